@@ -0,0 +1,71 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// Run with `cargo bench`. The `sum_*` functions are `#[inline(never)]` so
+// their code can also be inspected on its own, e.g. with `cargo asm`: the
+// slice versions compare every index against the length, while the array
+// versions have no bounds checks and unroll fully.
+
+#[macro_use] extern crate slice_as_array;
+extern crate criterion;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// Read the bytes in a scattered order, as a hash or cipher round would, so
+// that the compiler cannot hoist a single length check out of the loop.
+const ORDER: [usize; 32] = [
+    7, 20, 1, 31, 12, 25, 3, 16, 29, 9, 22, 5, 18, 0, 27, 14,
+    10, 23, 6, 19, 2, 30, 13, 26, 8, 21, 4, 17, 28, 11, 24, 15,
+];
+
+#[inline(never)]
+fn sum_slice(xs: &[u8]) -> u32 {
+    let mut sum = 0u32;
+    for &i in ORDER.iter() {
+        sum = sum.wrapping_mul(31).wrapping_add(xs[i] as u32);
+    }
+    sum
+}
+
+#[inline(never)]
+fn sum_array(xs: &[u8; 32]) -> u32 {
+    let mut sum = 0u32;
+    for &i in ORDER.iter() {
+        sum = sum.wrapping_mul(31).wrapping_add(xs[i] as u32);
+    }
+    sum
+}
+
+fn indexing(c: &mut Criterion) {
+    let bytes: Vec<u8> = (0..32).collect();
+    let mut group = c.benchmark_group("indexing");
+    group.bench_function("slice", |b| b.iter(|| sum_slice(black_box(&bytes[..]))));
+    group.bench_function("array", |b| b.iter(|| {
+        let array = slice_as_array!(black_box(&bytes[..]), [u8; 32]).unwrap();
+        sum_array(array)
+    }));
+    group.finish();
+}
+
+fn conversion(c: &mut Criterion) {
+    let bytes: Vec<u8> = (0..32).collect();
+    let mut group = c.benchmark_group("conversion");
+    group.bench_function("checked", |b| b.iter(|| slice_as_array!(black_box(&bytes[..]), [u8; 32]).map(|a| a[31])));
+    group.bench_function("unchecked", |b| b.iter(|| {
+        let array = unsafe { slice_as_array::slice_as_array_unchecked::<u8, 32>(black_box(&bytes[..])) };
+        array[31]
+    }));
+    group.bench_function("copy", |b| b.iter(|| slice_to_array_copy!(black_box(&bytes[..]), [u8; 32])));
+    group.bench_function("clone", |b| b.iter(|| slice_to_array_clone!(black_box(&bytes[..]), [u8; 32])));
+    group.finish();
+}
+
+criterion_group!(benches, indexing, conversion);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions from `arrayvec::ArrayVec`, behind the `arrayvec` feature.
+
+use arrayvec::ArrayVec;
+use {report_length_mismatch, slice_as_array};
+
+/// Move the elements of a full `ArrayVec` into an array.
+/// Returns the `ArrayVec` unchanged if it holds fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn arrayvec_into_array<T, const N: usize>(vec: ArrayVec<T, N>) -> Result<[T; N], ArrayVec<T, N>> {
+    match vec.into_inner() {
+        Ok(array) => Ok(array),
+        Err(vec) => {
+            report_length_mismatch::<T>(N, vec.len());
+            Err(vec)
+        }
+    }
+}
+
+/// Borrow the elements of a full `ArrayVec` as an array.
+/// Returns `None` if it holds fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn arrayvec_as_array<T, const N: usize>(vec: &ArrayVec<T, N>) -> Option<&[T; N]> {
+    slice_as_array(vec.as_slice())
+}
+
+#[cfg(test)]
+mod test {
+    use arrayvec::ArrayVec;
+    use super::{arrayvec_into_array, arrayvec_as_array};
+
+    #[test]
+    fn into_array() {
+        let mut xs: ArrayVec<u32, 3> = ArrayVec::new();
+        xs.push(1);
+        xs.push(2);
+        let mut xs = arrayvec_into_array(xs).unwrap_err();
+        xs.push(3);
+        assert_eq!(arrayvec_into_array(xs), Ok([1, 2, 3]));
+    }
+
+    #[test]
+    fn as_array() {
+        let mut xs: ArrayVec<u32, 2> = ArrayVec::new();
+        xs.push(1);
+        assert_eq!(arrayvec_as_array(&xs), None);
+        xs.push(2);
+        assert_eq!(arrayvec_as_array(&xs), Some(&[1, 2]));
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Building an owned array one element at a time.
+
+#[cfg(feature="use_std")] use std::fmt;
+#[cfg(not(feature="use_std"))] use core::fmt;
+use {report_length_mismatch, SafeArrayInitialization};
+
+/// Accumulates up to `N` values, and then turns into a `[T; N]`.
+/// Values pushed before the builder is dropped without being built are
+/// dropped with it.
+pub struct ArrayBuilder<T, const N: usize> {
+    init: SafeArrayInitialization<T, N>,
+}
+
+impl<T, const N: usize> ArrayBuilder<T, N> {
+    /// An empty builder.
+    #[inline]
+    pub fn new() -> Self {
+        ArrayBuilder { init: SafeArrayInitialization::new() }
+    }
+
+    /// Append a value, or hand it back if the builder already holds `N`.
+    #[inline]
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.init.push(value);
+        Ok(())
+    }
+
+    /// The number of values pushed so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.init.count
+    }
+
+    /// Whether no values have been pushed yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.init.count == 0
+    }
+
+    /// Whether `N` values have been pushed, so that `build` will succeed.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.init.count == N
+    }
+
+    /// The finished array, or the builder unchanged if it is not full yet.
+    #[inline]
+    #[track_caller]
+    pub fn build(self) -> Result<[T; N], Self> {
+        if !self.is_full() {
+            report_length_mismatch::<T>(N, self.len());
+            return Err(self);
+        }
+        Ok(self.init.finish())
+    }
+}
+
+impl<T, const N: usize> Default for ArrayBuilder<T, N> {
+    fn default() -> Self {
+        ArrayBuilder::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArrayBuilder<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ArrayBuilder")
+            .field("values", &self.init.as_slice())
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use super::ArrayBuilder;
+
+    #[derive(Debug)]
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl<'a> Drop for DropCounter<'a> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn build() {
+        let mut builder = ArrayBuilder::<String, 3>::new();
+        for word in "a b c d".split(' ') {
+            if let Err(extra) = builder.push(word.to_string()) {
+                assert_eq!(extra, "d");
+            }
+        }
+        assert!(builder.is_full());
+        assert_eq!(builder.build().unwrap(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn build_not_full() {
+        let mut builder = ArrayBuilder::<u8, 2>::default();
+        assert!(builder.is_empty());
+        builder.push(1).unwrap();
+        let mut builder = builder.build().unwrap_err();
+        assert_eq!(builder.len(), 1);
+        builder.push(2).unwrap();
+        assert_eq!(builder.build().unwrap(), [1, 2]);
+        assert_eq!(ArrayBuilder::<u8, 0>::new().build().unwrap(), []);
+    }
+
+    #[test]
+    fn drops_unbuilt() {
+        let drops = Cell::new(0);
+        let mut builder = ArrayBuilder::<_, 4>::new();
+        builder.push(DropCounter(&drops)).unwrap();
+        builder.push(DropCounter(&drops)).unwrap();
+        drop(builder);
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn debug() {
+        let mut builder = ArrayBuilder::<u8, 3>::new();
+        builder.push(7).unwrap();
+        assert_eq!(format!("{:?}", builder), "ArrayBuilder { values: [7], capacity: 3 }");
+        builder.push(8).unwrap();
+        builder.push(9).unwrap();
+        assert_eq!(builder.build().expect("builder is full"), [7, 8, 9]);
+    }
+}
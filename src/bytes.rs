@@ -0,0 +1,276 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions between bytes and other plain-old-data types or strings.
+
+#[cfg(feature="use_std")] use std::{array, mem, str};
+#[cfg(not(feature="use_std"))] use core::{array, mem, str};
+use {reexport, report_length_mismatch, report_mismatch, slice_as_array, Mismatch};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Types for which every bit pattern is a valid value and which have no
+/// padding, so that any correctly sized and aligned bytes can be read as one.
+///
+/// This is implemented for the primitive integer and floating-point types
+/// and for arrays of them, and cannot be implemented outside this crate.
+pub trait Pod: Copy + private::Sealed {}
+
+macro_rules! impl_pod {
+    ($($t:ty)*) => {
+        $(
+            impl private::Sealed for $t {}
+            impl Pod for $t {}
+        )*
+    }
+}
+
+impl_pod!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64);
+
+impl<U: Pod, const K: usize> private::Sealed for [U; K] {}
+impl<U: Pod, const K: usize> Pod for [U; K] {}
+
+/// Reinterpret a byte slice as an array of `N` plain-old-data values.
+/// Returns `None` if the slice is not exactly `N * size_of::<U>()` bytes
+/// long, or if it is not aligned for `U`.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn bytes_as_array<U: Pod, const N: usize>(bytes: &[u8]) -> Option<&[U; N]> {
+    if Some(bytes.len()) != N.checked_mul(mem::size_of::<U>()) {
+        report_length_mismatch::<u8>(N.saturating_mul(mem::size_of::<U>()), bytes.len());
+        return None;
+    }
+    let offset = bytes.as_ptr() as usize % mem::align_of::<U>();
+    if offset != 0 {
+        report_mismatch::<U>(N, Mismatch::Misaligned { align: mem::align_of::<U>(), offset });
+        return None;
+    }
+    let ptr = bytes.as_ptr() as *const [U; N];
+    reexport::debug_assert_aligned(ptr);
+    Some( unsafe { &*ptr } )
+}
+
+/// Reinterpret a byte slice as an array of plain-old-data values, checking length and alignment.
+/// `bytes_as_array!(bytes, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! bytes_as_array {
+    ($bytes:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[u8] = $crate::reexport::slice_arg(($bytes).slice_arg_ref());
+        $crate::bytes_as_array::<$t, { $len }>(s)
+    }}
+}
+
+/// Primitive integer types that can be decoded from bytes with either endianness.
+///
+/// This cannot be implemented outside this crate.
+pub trait Integer: Pod {
+    #[doc(hidden)]
+    fn from_le_slice(bytes: &[u8]) -> Self;
+    #[doc(hidden)]
+    fn from_be_slice(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ident)*) => {
+        $(
+            impl Integer for $t {
+                #[inline]
+                fn from_le_slice(bytes: &[u8]) -> Self {
+                    $t::from_le_bytes(*slice_as_array(bytes).unwrap())
+                }
+                #[inline]
+                fn from_be_slice(bytes: &[u8]) -> Self {
+                    $t::from_be_bytes(*slice_as_array(bytes).unwrap())
+                }
+            }
+        )*
+    }
+}
+
+impl_integer!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128);
+
+#[inline]
+#[track_caller]
+fn slice_to_int_array<U: Integer, F: Fn(&[u8]) -> U, const N: usize>(bytes: &[u8], decode: F) -> Option<[U; N]> {
+    let size = mem::size_of::<U>();
+    if Some(bytes.len()) != N.checked_mul(size) {
+        report_length_mismatch::<u8>(N.saturating_mul(size), bytes.len());
+        return None;
+    }
+    Some(array::from_fn(|i| decode(&bytes[i * size..(i + 1) * size])))
+}
+
+/// Decode a byte slice into an array of `N` little-endian integers.
+/// Returns `None` if the slice is not exactly `N * size_of::<U>()` bytes long.
+/// The bytes need not be aligned.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_to_int_array_le<U: Integer, const N: usize>(bytes: &[u8]) -> Option<[U; N]> {
+    slice_to_int_array(bytes, U::from_le_slice)
+}
+
+/// Decode a byte slice into an array of `N` big-endian integers.
+/// Returns `None` if the slice is not exactly `N * size_of::<U>()` bytes long.
+/// The bytes need not be aligned.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_to_int_array_be<U: Integer, const N: usize>(bytes: &[u8]) -> Option<[U; N]> {
+    slice_to_int_array(bytes, U::from_be_slice)
+}
+
+/// Decode a byte slice into an array of little-endian integers.
+/// `slice_to_int_array_le!(bytes, [integer_type; array_length]) -> Option<[integer_type; array_length]>`
+#[macro_export]
+macro_rules! slice_to_int_array_le {
+    ($bytes:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[u8] = $crate::reexport::slice_arg(($bytes).slice_arg_ref());
+        $crate::slice_to_int_array_le::<$t, { $len }>(s)
+    }}
+}
+
+/// Decode a byte slice into an array of big-endian integers.
+/// `slice_to_int_array_be!(bytes, [integer_type; array_length]) -> Option<[integer_type; array_length]>`
+#[macro_export]
+macro_rules! slice_to_int_array_be {
+    ($bytes:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[u8] = $crate::reexport::slice_arg(($bytes).slice_arg_ref());
+        $crate::slice_to_int_array_be::<$t, { $len }>(s)
+    }}
+}
+
+/// Borrow the UTF-8 bytes of a string as an array.
+/// Returns `None` if the string is not exactly `N` bytes long. Note that `N`
+/// counts bytes, not `char`s: `"héllo"` is 6 bytes.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn str_as_byte_array<const N: usize>(s: &str) -> Option<&[u8; N]> {
+    slice_as_array(s.as_bytes())
+}
+
+/// Borrow an array of bytes as a string, checking that it is valid UTF-8.
+#[inline]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn byte_array_as_str<const N: usize>(bytes: &[u8; N]) -> Result<&str, str::Utf8Error> {
+    str::from_utf8(bytes)
+}
+
+/// Borrow the UTF-8 bytes of a string as an array of the given byte length.
+/// `str_as_byte_array!(string, byte_length) -> Option<&[u8; byte_length]>`
+#[macro_export]
+macro_rules! str_as_byte_array {
+    ($s:expr, $len:expr ) => {{
+        let s: &str = $s;
+        $crate::str_as_byte_array::<{ $len }>(s)
+    }}
+}
+
+/// Borrow an array of bytes as a string, checking that it is valid UTF-8.
+/// `byte_array_as_str!(&[u8; byte_length]) -> Result<&str, Utf8Error>`
+#[macro_export]
+macro_rules! byte_array_as_str {
+    ($bytes:expr) => {{
+        $crate::byte_array_as_str($bytes)
+    }}
+}
+
+#[cfg(test)]
+mod test {
+    use std::slice;
+
+    // Backed by `u64`s so that the bytes are aligned for every tested type.
+    fn aligned_bytes(words: &[u64; 2]) -> &[u8] {
+        unsafe { slice::from_raw_parts(words.as_ptr() as *const u8, 16) }
+    }
+
+    #[test]
+    fn bytes_as_array() {
+        let words: [u64; 2] = [u64::from_ne_bytes([1, 0, 2, 0, 3, 0, 4, 0]), 0];
+        let bytes = aligned_bytes(&words);
+        let halves: &[u16; 4] = bytes_as_array!(&bytes[..8], [u16; 4]).unwrap();
+        assert_eq!(halves, &[u16::from_ne_bytes([1, 0]), u16::from_ne_bytes([2, 0]), u16::from_ne_bytes([3, 0]), u16::from_ne_bytes([4, 0])]);
+    }
+
+    #[test]
+    fn bytes_as_array_wrong_length() {
+        let words: [u64; 2] = [0, 0];
+        let bytes = aligned_bytes(&words);
+        assert_eq!(bytes_as_array!(&bytes[..7], [u16; 4]), None);
+        assert_eq!(bytes_as_array!(&bytes[..9], [u16; 4]), None);
+    }
+
+    #[test]
+    fn bytes_as_array_misaligned() {
+        let words: [u64; 2] = [0, 0];
+        let bytes = aligned_bytes(&words);
+        assert!(bytes_as_array!(&bytes[4..12], [u32; 2]).is_some());
+        assert_eq!(bytes_as_array!(&bytes[1..9], [u32; 2]), None);
+    }
+
+    #[test]
+    fn bytes_as_nested_array() {
+        let words: [u64; 2] = [0, 0];
+        let bytes = aligned_bytes(&words);
+        let pairs: &[[u32; 2]; 2] = bytes_as_array!(bytes, [[u32; 2]; 2]).unwrap();
+        assert_eq!(pairs, &[[0, 0], [0, 0]]);
+    }
+
+    #[test]
+    fn int_array_le() {
+        let bytes: [u8; 9] = [0, 1, 0, 0, 0, 2, 0, 0, 0];
+        assert_eq!(slice_to_int_array_le!(&bytes[1..], [u32; 2]), Some([1, 2]));
+        assert_eq!(slice_to_int_array_le!(&bytes[1..], [u16; 4]), Some([1, 0, 2, 0]));
+        assert_eq!(slice_to_int_array_le!(&bytes[1..], [i64; 1]), Some([0x0000_0002_0000_0001]));
+    }
+
+    #[test]
+    fn int_array_be() {
+        let bytes: [u8; 8] = [0, 0, 0, 1, 0xff, 0xff, 0xff, 0xfe];
+        assert_eq!(slice_to_int_array_be!(&bytes[..], [u32; 2]), Some([1, 0xffff_fffe]));
+        assert_eq!(slice_to_int_array_be!(&bytes[..], [i32; 2]), Some([1, -2]));
+        assert_eq!(slice_to_int_array_be!(&bytes[4..], [i16; 2]), Some([-1, -2]));
+        assert_eq!(slice_to_int_array_be!(&bytes[..], [u64; 1]), Some([0x0000_0001_ffff_fffe]));
+    }
+
+    #[test]
+    fn int_array_wrong_length() {
+        let bytes: [u8; 7] = [0; 7];
+        assert_eq!(slice_to_int_array_le!(&bytes[..], [u32; 2]), None);
+        assert_eq!(slice_to_int_array_be!(&bytes[..], [u16; 3]), None);
+    }
+
+    #[test]
+    fn str_as_byte_array() {
+        assert_eq!(str_as_byte_array!("abcd", 4), Some(b"abcd"));
+        assert_eq!(str_as_byte_array!("abcd", 3), None);
+    }
+
+    #[test]
+    fn str_as_byte_array_multibyte() {
+        // Five chars, but six bytes.
+        let s = "h\u{e9}llo";
+        assert_eq!(str_as_byte_array!(s, 5), None);
+        let bytes: &[u8; 6] = str_as_byte_array!(s, 6).unwrap();
+        assert_eq!(byte_array_as_str!(bytes), Ok(s));
+    }
+
+    #[test]
+    fn byte_array_as_str_invalid() {
+        let bytes: [u8; 2] = [0xc3, 0x28];
+        assert!(byte_array_as_str!(&bytes).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The length checks used by the conversions, for validating lengths ahead of
+//! time with exactly the same semantics.
+
+/// Whether the slice has exactly `n` elements, so that it converts to `[T; n]`.
+#[inline]
+pub const fn has_len<T>(slice: &[T], n: usize) -> bool {
+    slice.len() == n
+}
+
+/// Whether the slice has at least `n` elements, so that an `[T; n]` can be
+/// split off either end of it.
+#[inline]
+pub const fn has_at_least<T>(slice: &[T], n: usize) -> bool {
+    slice.len() >= n
+}
+
+/// Whether the slice splits into chunks of `n` elements with none left over.
+/// Always `false` for an `n` of 0.
+#[inline]
+pub const fn divides_evenly<T>(slice: &[T], n: usize) -> bool {
+    n != 0 && slice.len() % n == 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::{has_len, has_at_least, divides_evenly};
+
+    #[test]
+    fn predicates() {
+        let xs = [0u8; 6];
+        assert!(has_len(&xs, 6) && !has_len(&xs, 5));
+        assert!(has_at_least(&xs, 6) && has_at_least(&xs, 0) && !has_at_least(&xs, 7));
+        assert!(divides_evenly(&xs, 3) && !divides_evenly(&xs, 4) && !divides_evenly(&xs, 0));
+        assert!(divides_evenly(&xs[..0], 4));
+    }
+
+    #[test]
+    fn const_context() {
+        const TABLE: &[u16] = &[1, 2, 3, 4];
+        const PAIRS: usize = if divides_evenly(TABLE, 2) { TABLE.len() / 2 } else { 0 };
+        const HAS_LEN: [bool; 2] = [has_len(TABLE, 4), has_at_least(TABLE, 5)];
+        assert_eq!((PAIRS, HAS_LEN), (2, [true, false]));
+    }
+}
@@ -0,0 +1,497 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Iterators over fixed-size pieces of a slice.
+
+#[cfg(feature="use_std")] use std::slice;
+#[cfg(not(feature="use_std"))] use core::slice;
+use {check, reexport, report_mismatch, slice_as_array_prefix, Mismatch};
+
+/// An iterator over `&[T; N]` chunks of a slice, starting at the beginning.
+/// Elements left over at the end are available from `remainder`.
+///
+/// Created by `array_chunks_ref!(slice, N)` or `array_chunks_ref::<T, N>(slice)`.
+pub struct ArrayChunks<'a, T: 'a, const N: usize> {
+    chunks: slice::Iter<'a, [T; N]>,
+    remainder: &'a [T],
+}
+
+impl<'a, T, const N: usize> ArrayChunks<'a, T, N> {
+    /// The elements at the end of the slice that do not fill a whole chunk.
+    pub fn remainder(&self) -> &'a [T] {
+        self.remainder
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for ArrayChunks<'a, T, N> {
+    type Item = &'a [T; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [T; N]> {
+        self.chunks.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<&'a [T; N]> {
+        self.chunks.nth(n)
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for ArrayChunks<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a [T; N]> {
+        self.chunks.next_back()
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for ArrayChunks<'a, T, N> {}
+
+/// Iterate over `&[T; N]` chunks of a slice, leaving any partial chunk at the end
+/// in the iterator's `remainder`.
+///
+/// # Panics
+///
+/// Panics if `N` is 0.
+pub fn array_chunks_ref<'a, T, const N: usize>(slice: &'a [T]) -> ArrayChunks<'a, T, N> {
+    assert!(N != 0, "chunk size must be non-zero");
+    let count = slice.len() / N;
+    let (chunks, remainder) = slice.split_at(count * N);
+    // `count` contiguous runs of `N` elements have the same layout as `[[T; N]; count]`.
+    let ptr = chunks.as_ptr() as *const [T; N];
+    reexport::debug_assert_aligned(ptr);
+    let chunks = unsafe { slice::from_raw_parts(ptr, count) };
+    ArrayChunks { chunks: chunks.iter(), remainder }
+}
+
+/// Iterate over fixed-size array chunks of a slice.
+/// `array_chunks_ref!(slice, chunk_length) -> ArrayChunks<element_type, chunk_length>`
+#[macro_export]
+macro_rules! array_chunks_ref {
+    ($slice:expr, $len:expr ) => {{
+        $crate::array_chunks_ref::<_, { $len }>($slice)
+    }}
+}
+
+/// An iterator over the `&[T; N]` chunks of a slice whose length is a
+/// multiple of `N`, so that there is no remainder.
+///
+/// Created by `exact_array_chunks!(slice, N)` or `exact_array_chunks::<T, N>(slice)`.
+pub struct ExactArrayChunks<'a, T: 'a, const N: usize> {
+    chunks: slice::Iter<'a, [T; N]>,
+}
+
+impl<'a, T, const N: usize> Clone for ExactArrayChunks<'a, T, N> {
+    fn clone(&self) -> Self {
+        ExactArrayChunks { chunks: self.chunks.clone() }
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for ExactArrayChunks<'a, T, N> {
+    type Item = &'a [T; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [T; N]> {
+        self.chunks.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<&'a [T; N]> {
+        self.chunks.nth(n)
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for ExactArrayChunks<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a [T; N]> {
+        self.chunks.next_back()
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for ExactArrayChunks<'a, T, N> {}
+
+/// Iterate over `&[T; N]` chunks of a slice that splits evenly into them.
+/// Returns `None` if the length of the slice is not a multiple of `N`.
+///
+/// # Panics
+///
+/// Panics if `N` is 0.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn exact_array_chunks<'a, T, const N: usize>(slice: &'a [T]) -> Option<ExactArrayChunks<'a, T, N>> {
+    let chunks = array_chunks_ref(slice);
+    if check::divides_evenly(slice, N) {
+        Some(ExactArrayChunks { chunks: chunks.chunks })
+    } else {
+        report_mismatch::<T>(N, Mismatch::NotMultiple(slice.len()));
+        None
+    }
+}
+
+/// Iterate over the array chunks of a slice, requiring that none are left over.
+/// `exact_array_chunks!(slice, chunk_length) -> Option<ExactArrayChunks<element_type, chunk_length>>`
+#[macro_export]
+macro_rules! exact_array_chunks {
+    ($slice:expr, $len:expr ) => {{
+        $crate::exact_array_chunks::<_, { $len }>($slice)
+    }}
+}
+
+/// An iterator over `&[T; N]` chunks of a slice, starting at the end.
+/// Elements left over at the beginning are available from `remainder`.
+///
+/// Created by `rarray_chunks!(slice, N)` or `rarray_chunks::<T, N>(slice)`.
+pub struct RArrayChunks<'a, T: 'a, const N: usize> {
+    chunks: slice::Iter<'a, [T; N]>,
+    remainder: &'a [T],
+}
+
+impl<'a, T, const N: usize> RArrayChunks<'a, T, N> {
+    /// The elements at the beginning of the slice that do not fill a whole chunk.
+    pub fn remainder(&self) -> &'a [T] {
+        self.remainder
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for RArrayChunks<'a, T, N> {
+    type Item = &'a [T; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [T; N]> {
+        self.chunks.next_back()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<&'a [T; N]> {
+        self.chunks.nth_back(n)
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for RArrayChunks<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a [T; N]> {
+        self.chunks.next()
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for RArrayChunks<'a, T, N> {}
+
+/// Iterate over `&[T; N]` chunks of a slice from the end, leaving any partial
+/// chunk at the beginning in the iterator's `remainder`.
+///
+/// # Panics
+///
+/// Panics if `N` is 0.
+pub fn rarray_chunks<'a, T, const N: usize>(slice: &'a [T]) -> RArrayChunks<'a, T, N> {
+    assert!(N != 0, "chunk size must be non-zero");
+    let (remainder, chunks) = slice.split_at(slice.len() % N);
+    RArrayChunks { chunks: array_chunks_ref(chunks).chunks, remainder }
+}
+
+/// Iterate over fixed-size array chunks of a slice, starting from the end.
+/// `rarray_chunks!(slice, chunk_length) -> RArrayChunks<element_type, chunk_length>`
+#[macro_export]
+macro_rules! rarray_chunks {
+    ($slice:expr, $len:expr ) => {{
+        $crate::rarray_chunks::<_, { $len }>($slice)
+    }}
+}
+
+/// An iterator over non-overlapping `&mut [T; N]` chunks of a slice, starting
+/// at the beginning. Elements left over at the end are available from
+/// `remainder_mut`.
+///
+/// Created by `array_chunks_mut!(slice, N)` or `array_chunks_mut::<T, N>(slice)`.
+pub struct ArrayChunksMut<'a, T: 'a, const N: usize> {
+    chunks: slice::IterMut<'a, [T; N]>,
+    remainder: &'a mut [T],
+}
+
+impl<'a, T, const N: usize> ArrayChunksMut<'a, T, N> {
+    /// The elements at the end of the slice that do not fill a whole chunk.
+    pub fn remainder_mut(&mut self) -> &mut [T] {
+        self.remainder
+    }
+
+    /// The elements at the end of the slice that do not fill a whole chunk,
+    /// borrowed for as long as the original slice.
+    pub fn into_remainder(self) -> &'a mut [T] {
+        self.remainder
+    }
+}
+
+impl<'a, T, const N: usize> Iterator for ArrayChunksMut<'a, T, N> {
+    type Item = &'a mut [T; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a mut [T; N]> {
+        self.chunks.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<&'a mut [T; N]> {
+        self.chunks.nth(n)
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for ArrayChunksMut<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a mut [T; N]> {
+        self.chunks.next_back()
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for ArrayChunksMut<'a, T, N> {}
+
+/// Iterate over `&mut [T; N]` chunks of a slice, leaving any partial chunk at
+/// the end in the iterator's `remainder_mut`.
+///
+/// # Panics
+///
+/// Panics if `N` is 0.
+pub fn array_chunks_mut<'a, T, const N: usize>(slice: &'a mut [T]) -> ArrayChunksMut<'a, T, N> {
+    assert!(N != 0, "chunk size must be non-zero");
+    let count = slice.len() / N;
+    let (chunks, remainder) = slice.split_at_mut(count * N);
+    let ptr = chunks.as_mut_ptr() as *mut [T; N];
+    reexport::debug_assert_aligned(ptr);
+    let chunks = unsafe { slice::from_raw_parts_mut(ptr, count) };
+    ArrayChunksMut { chunks: chunks.iter_mut(), remainder }
+}
+
+/// Iterate over fixed-size mutable array chunks of a slice.
+/// `array_chunks_mut!(mutable_slice, chunk_length) -> ArrayChunksMut<element_type, chunk_length>`
+#[macro_export]
+macro_rules! array_chunks_mut {
+    ($slice:expr, $len:expr ) => {{
+        $crate::array_chunks_mut::<_, { $len }>($slice)
+    }}
+}
+
+/// An iterator over overlapping `&[T; N]` windows of a slice, advancing one
+/// element at a time.
+///
+/// Created by `windows_as_arrays!(slice, N)` or `array_windows::<T, N>(slice)`.
+pub struct ArrayWindows<'a, T: 'a, const N: usize> {
+    // The windows left are those starting at `slice[0..count]`.
+    slice: &'a [T],
+    count: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for ArrayWindows<'a, T, N> {
+    type Item = &'a [T; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [T; N]> {
+        if self.count == 0 {
+            return None;
+        }
+        let window = slice_as_array_prefix(self.slice);
+        self.count -= 1;
+        if self.count > 0 {
+            self.slice = &self.slice[1..];
+        }
+        window
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.count, Some(self.count))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for ArrayWindows<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a [T; N]> {
+        if self.count == 0 {
+            return None;
+        }
+        self.count -= 1;
+        slice_as_array_prefix(&self.slice[self.count..])
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for ArrayWindows<'a, T, N> {}
+
+/// Iterate over every `&[T; N]` window of a slice. There are
+/// `slice.len() - N + 1` of them, or none if the slice is shorter than `N`.
+pub fn array_windows<'a, T, const N: usize>(slice: &'a [T]) -> ArrayWindows<'a, T, N> {
+    let count = if check::has_at_least(slice, N) { slice.len() - N + 1 } else { 0 };
+    ArrayWindows { slice, count }
+}
+
+/// Iterate over overlapping fixed-size array windows of a slice.
+/// `windows_as_arrays!(slice, window_length) -> ArrayWindows<element_type, window_length>`
+#[macro_export]
+macro_rules! windows_as_arrays {
+    ($slice:expr, $len:expr ) => {{
+        $crate::array_windows::<_, { $len }>($slice)
+    }}
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn chunks() {
+        let xs: [u8; 7] = [1, 2, 3, 4, 5, 6, 7];
+        let mut chunks = array_chunks_ref!(&xs[..], 3);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.next(), Some(&[1, 2, 3]));
+        assert_eq!(chunks.next(), Some(&[4, 5, 6]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.remainder(), &[7]);
+    }
+
+    #[test]
+    fn chunks_back() {
+        let xs: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+        let chunks: Vec<&[u8; 2]> = array_chunks_ref!(&xs[..], 2).rev().collect();
+        assert_eq!(chunks, [&[7, 8], &[5, 6], &[3, 4], &[1, 2]]);
+    }
+
+    #[test]
+    fn chunks_short() {
+        let xs: [u8; 2] = [1, 2];
+        let chunks = array_chunks_ref!(&xs[..], 3);
+        assert_eq!(chunks.remainder(), &[1, 2]);
+        assert_eq!(chunks.count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn chunks_zero_length() {
+        let xs: [u8; 2] = [1, 2];
+        array_chunks_ref!(&xs[..], 0);
+    }
+
+    #[test]
+    fn chunks_mut() {
+        fn mutate_chunk(xs: &mut [u32; 10]) {
+            xs[9] = xs[0] + 1;
+        }
+
+        let mut xs = [0u32; 100];
+        for (i, chunk) in array_chunks_mut!(&mut xs[..], 10).enumerate() {
+            chunk[0] = i as u32;
+            mutate_chunk(chunk);
+        }
+        assert_eq!(xs[9], 1);
+        assert_eq!(xs[99], 10);
+    }
+
+    #[test]
+    fn chunks_mut_remainder() {
+        let mut xs: [u8; 5] = [1, 2, 3, 4, 5];
+        {
+            let mut chunks = array_chunks_mut!(&mut xs[..], 2);
+            assert_eq!(chunks.len(), 2);
+            chunks.remainder_mut()[0] = 0;
+            let last = chunks.next_back().unwrap();
+            let first = chunks.next().unwrap();
+            first[0] = last[1];
+            assert_eq!(chunks.into_remainder(), &[0]);
+        }
+        assert_eq!(xs, [4, 2, 3, 4, 0]);
+    }
+
+    #[test]
+    fn windows() {
+        let xs: [u8; 5] = [1, 2, 3, 4, 5];
+        let mut windows = windows_as_arrays!(&xs[..], 3);
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows.next(), Some(&[1, 2, 3]));
+        assert_eq!(windows.next_back(), Some(&[3, 4, 5]));
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows.next(), Some(&[2, 3, 4]));
+        assert_eq!(windows.next(), None);
+        assert_eq!(windows.next_back(), None);
+    }
+
+    #[test]
+    fn windows_exact_length() {
+        let xs: [u8; 3] = [1, 2, 3];
+        let windows: Vec<&[u8; 3]> = windows_as_arrays!(&xs[..], 3).collect();
+        assert_eq!(windows, [&[1, 2, 3]]);
+    }
+
+    #[test]
+    fn windows_short() {
+        let xs: [u8; 2] = [1, 2];
+        assert_eq!(windows_as_arrays!(&xs[..], 3).len(), 0);
+        assert_eq!(windows_as_arrays!(&xs[..], 3).next(), None);
+    }
+
+    #[test]
+    fn windows_zero_length() {
+        let xs: [u8; 2] = [1, 2];
+        assert_eq!(windows_as_arrays!(&xs[..], 0).count(), 3);
+        assert_eq!(windows_as_arrays!(&xs[..0], 0).rev().count(), 1);
+    }
+
+    #[test]
+    fn zero_sized_chunks() {
+        let xs = [(); 7];
+        let mut chunks = array_chunks_ref!(&xs[..], 3);
+        assert_eq!(chunks.by_ref().count(), 2);
+        assert_eq!(chunks.remainder().len(), 1);
+    }
+
+    #[test]
+    fn exact_chunks() {
+        let xs: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        let chunks = exact_array_chunks!(&xs[..], 2).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.clone().rev().collect::<Vec<_>>(), [&[5, 6], &[3, 4], &[1, 2]]);
+        let mut chunks = chunks;
+        assert_eq!(chunks.next(), Some(&[1, 2]));
+        assert_eq!(chunks.next_back(), Some(&[5, 6]));
+        assert_eq!(chunks.collect::<Vec<_>>(), [&[3, 4]]);
+    }
+
+    #[test]
+    fn exact_chunks_remainder() {
+        let xs: [u8; 7] = [1, 2, 3, 4, 5, 6, 7];
+        assert!(exact_array_chunks!(&xs[..], 2).is_none());
+        assert_eq!(exact_array_chunks!(&xs[..0], 2).map(|c| c.len()), Some(0));
+    }
+
+    #[test]
+    fn rchunks() {
+        let xs: [u8; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut chunks = rarray_chunks!(&xs[..], 3);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.remainder(), &[0]);
+        assert_eq!(chunks.next(), Some(&[7, 8, 9]));
+        assert_eq!(chunks.next_back(), Some(&[1, 2, 3]));
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks.next(), Some(&[4, 5, 6]));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(rarray_chunks!(&xs[..], 5).nth(1), Some(&[0, 1, 2, 3, 4]));
+        assert_eq!(rarray_chunks!(&xs[..2], 3).remainder(), &[0, 1]);
+    }
+}
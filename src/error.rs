@@ -0,0 +1,145 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Errors returned by the `Result`-returning conversions.
+
+#[cfg(feature="use_std")] use std::{any, fmt};
+#[cfg(not(feature="use_std"))] use core::{any, fmt};
+
+/// A slice did not have the length required by the conversion.
+///
+/// Its `Display` output reads "slice length mismatch: expected 4, found 7".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SliceLengthError {
+    /// The length the conversion required.
+    pub expected: usize,
+    /// The length of the slice that was passed in.
+    pub actual: usize,
+}
+
+impl fmt::Display for SliceLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "slice length mismatch: expected {}, found {}", self.expected, self.actual)
+    }
+}
+
+#[cfg(feature="use_std")]
+impl ::std::error::Error for SliceLengthError {}
+
+// `core::error::Error` needs Rust 1.81; with std it is the same trait as above.
+#[cfg(not(feature="use_std"))]
+impl ::core::error::Error for SliceLengthError {}
+
+impl SliceLengthError {
+    /// The same error, also naming the element type `T` in its message.
+    pub fn with_type<T: ?Sized>(expected: usize, actual: usize) -> ConversionError {
+        ConversionError { length: SliceLengthError { expected, actual }, element_type: any::type_name::<T>() }
+    }
+}
+
+/// A slice did not have the length required to convert it to an array of
+/// a particular element type. Created by `SliceLengthError::with_type`.
+///
+/// Its `Display` output reads "expected [u8; 32], found length 20". The type
+/// name comes from `core::any::type_name`, so its exact text is unspecified.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ConversionError {
+    /// The required and actual lengths.
+    pub length: SliceLengthError,
+    /// The name of the array's element type.
+    pub element_type: &'static str,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected [{}; {}], found length {}", self.element_type, self.length.expected, self.length.actual)
+    }
+}
+
+impl From<ConversionError> for SliceLengthError {
+    fn from(err: ConversionError) -> SliceLengthError {
+        err.length
+    }
+}
+
+#[cfg(feature="use_std")]
+impl ::std::error::Error for ConversionError {}
+
+#[cfg(not(feature="use_std"))]
+impl ::core::error::Error for ConversionError {}
+
+/// A fallible iterator could not be collected into an array, or a slice
+/// could not be mapped into one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TryArrayError<E> {
+    /// The iterator yielded this error, or the mapping function returned it
+    /// for an element, before the array was full.
+    Item(E),
+    /// There were not exactly `expected` elements. For an iterator, it ended
+    /// early and `actual` is the number of items it yielded; for a slice,
+    /// `actual` is its length, and no element was mapped.
+    Length(SliceLengthError),
+}
+
+impl<E: fmt::Display> fmt::Display for TryArrayError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TryArrayError::Item(ref err) => err.fmt(f),
+            TryArrayError::Length(ref err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature="use_std")]
+impl<E: ::std::error::Error> ::std::error::Error for TryArrayError<E> {}
+
+#[cfg(not(feature="use_std"))]
+impl<E: ::core::error::Error> ::core::error::Error for TryArrayError<E> {}
+
+/// A slice could not be mapped into an array by `slice_map_to_array`.
+/// `Length` means the slice's length, `actual`, was wrong, and is returned
+/// before any element is mapped; `Item` holds the first error the mapping
+/// function returned.
+pub type MapArrayError<E> = TryArrayError<E>;
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+    use super::{ConversionError, SliceLengthError, TryArrayError};
+
+    #[test]
+    fn display() {
+        let err = SliceLengthError { expected: 4, actual: 7 };
+        assert_eq!(err.to_string(), "slice length mismatch: expected 4, found 7");
+    }
+
+    #[test]
+    fn boxed_error() {
+        fn fails() -> Result<(), Box<dyn Error>> {
+            Err(SliceLengthError { expected: 32, actual: 20 })?
+        }
+        assert_eq!(fails().unwrap_err().to_string(), "slice length mismatch: expected 32, found 20");
+    }
+
+    #[test]
+    fn try_array_display() {
+        let err: TryArrayError<SliceLengthError> = TryArrayError::Length(SliceLengthError { expected: 4, actual: 2 });
+        assert_eq!(err.to_string(), "slice length mismatch: expected 4, found 2");
+        let err = TryArrayError::Item("bad digit".parse::<u8>().unwrap_err());
+        assert_eq!(err.to_string(), "invalid digit found in string");
+    }
+
+    #[test]
+    fn with_type() {
+        let err: ConversionError = SliceLengthError::with_type::<u8>(32, 20);
+        assert_eq!(err.to_string(), "expected [u8; 32], found length 20");
+        assert_eq!(SliceLengthError::from(err), SliceLengthError { expected: 32, actual: 20 });
+        assert!(SliceLengthError::with_type::<String>(1, 0).element_type.ends_with("String"));
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions that panic with a caller-supplied message on a length mismatch.
+
+use {slice_as_array, slice_as_array_mut, slice_to_array_clone, SliceLengthError};
+
+#[cold]
+#[track_caller]
+fn length_mismatch(msg: &str, expected: usize, actual: usize) -> ! {
+    panic!("{}: {}", msg, SliceLengthError { expected, actual })
+}
+
+/// Convert a slice to an array.
+///
+/// # Panics
+///
+/// Panics with `msg` and both lengths if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+pub fn slice_as_array_expect<'a, T, const N: usize>(slice: &'a [T], msg: &str) -> &'a [T; N] {
+    match slice_as_array(slice) {
+        Some(array) => array,
+        None => length_mismatch(msg, N, slice.len()),
+    }
+}
+
+/// Convert a mutable slice to a mutable array.
+///
+/// # Panics
+///
+/// Panics with `msg` and both lengths if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+pub fn slice_as_array_mut_expect<'a, T, const N: usize>(slice: &'a mut [T], msg: &str) -> &'a mut [T; N] {
+    let actual = slice.len();
+    match slice_as_array_mut(slice) {
+        Some(array) => array,
+        None => length_mismatch(msg, N, actual),
+    }
+}
+
+/// Convert a slice to an array by cloning each element.
+///
+/// # Panics
+///
+/// Panics with `msg` and both lengths if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+pub fn slice_to_array_clone_expect<T: Clone, const N: usize>(slice: &[T], msg: &str) -> [T; N] {
+    match slice_to_array_clone(slice) {
+        Some(array) => array,
+        None => length_mismatch(msg, N, slice.len()),
+    }
+}
+
+/// Convert a slice to an array, panicking with a message on a length mismatch.
+/// `slice_as_array_expect!(slice, [element_type; array_length], message) -> &[element_type; array_length]`
+#[macro_export]
+macro_rules! slice_as_array_expect {
+    ($slice:expr, [$t:ty ; $len:expr], $msg:expr ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_expect::<$t, { $len }>(s, $msg)
+    }}
+}
+
+/// Convert a mutable slice to a mutable array, panicking with a message on a length mismatch.
+/// `slice_as_array_mut_expect!(mutable_slice, [element_type; array_length], message) -> &mut [element_type; array_length]`
+#[macro_export]
+macro_rules! slice_as_array_mut_expect {
+    ($slice:expr, [$t:ty ; $len:expr], $msg:expr ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_mut_expect::<$t, { $len }>(s, $msg)
+    }}
+}
+
+/// Clone a slice into an array, panicking with a message on a length mismatch.
+/// `slice_to_array_clone_expect!(slice, [element_type; array_length], message) -> [element_type; array_length]`
+#[macro_export]
+macro_rules! slice_to_array_clone_expect {
+    ($slice:expr, [$t:ty ; $len:expr], $msg:expr ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_clone_expect::<$t, { $len }>(s, $msg)
+    }}
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn expect() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        assert_eq!(slice_as_array_expect!(&xs[..2], [u32; 2], "bad length"), &[1, 2]);
+        assert_eq!(slice_to_array_clone_expect!(&xs[4..], [u32; 2], "bad length"), [16, 32]);
+        let boxed: Box<[u32]> = Box::new(xs);
+        assert_eq!(slice_as_array_expect!(boxed, [u32; 6], "bad length"), &xs);
+        let mut ys = xs;
+        slice_as_array_mut_expect!(&mut ys[..1], [u32; 1], "bad length")[0] = 0;
+        assert_eq!(ys[0], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "bad hash length: slice length mismatch: expected 4, found 6")]
+    fn expect_wrong_length() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        slice_as_array_expect!(&xs, [u32; 4], "bad hash length");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 7, found 6")]
+    fn expect_mut_wrong_length() {
+        let mut xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        slice_as_array_mut_expect!(&mut xs, [u32; 7], "bad length");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 5, found 6")]
+    fn expect_clone_wrong_length() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        slice_to_array_clone_expect!(&xs, [u32; 5], "bad length");
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Method-call forms of the slice conversions.
+
+use {slice_as_array, slice_as_array_mut, slice_to_array_clone};
+
+/// Extension methods to convert a slice to an array.
+///
+/// `xs[1..4].to_array_ref::<3>()` is equivalent to `slice_as_array!(&xs[1..4], [T; 3])`.
+/// The names differ from the inherent `as_array` and `as_array_mut` of recent
+/// toolchains, which would otherwise be picked over these.
+pub trait SliceAsArray {
+    /// The element type of the slice.
+    type Item;
+
+    /// Convert a slice to an array.
+    /// Returns `None` if the slice does not have exactly `N` elements.
+    #[must_use = "converting to an array produces a value that should be used"]
+    fn to_array_ref<const N: usize>(&self) -> Option<&[Self::Item; N]>;
+
+    /// Convert a mutable slice to a mutable array.
+    /// Returns `None` if the slice does not have exactly `N` elements.
+    #[must_use = "converting to an array produces a value that should be used"]
+    fn to_array_mut<const N: usize>(&mut self) -> Option<&mut [Self::Item; N]>;
+
+    /// Convert a slice to an array by cloning each element.
+    /// Returns `None` if the slice does not have exactly `N` elements.
+    #[must_use = "converting to an array produces a value that should be used"]
+    fn to_array_clone<const N: usize>(&self) -> Option<[Self::Item; N]> where Self::Item: Clone;
+}
+
+impl<T> SliceAsArray for [T] {
+    type Item = T;
+
+    #[inline]
+    #[track_caller]
+    fn to_array_ref<const N: usize>(&self) -> Option<&[T; N]> {
+        slice_as_array(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn to_array_mut<const N: usize>(&mut self) -> Option<&mut [T; N]> {
+        slice_as_array_mut(self)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn to_array_clone<const N: usize>(&self) -> Option<[T; N]> where T: Clone {
+        slice_to_array_clone(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use super::SliceAsArray;
+
+    #[test]
+    fn to_array_ref() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        assert_eq!(xs[1..4].to_array_ref::<3>(), Some(&[2, 4, 8]));
+        assert_eq!(xs[1..4].to_array_ref::<4>(), None);
+    }
+
+    #[test]
+    fn to_array_mut() {
+        let mut xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        xs[1..4].to_array_mut::<3>().unwrap()[2] = 9;
+        assert_eq!(xs, [1, 2, 4, 9, 16, 32]);
+        assert_eq!(xs[1..4].to_array_mut::<2>(), None);
+    }
+
+    #[test]
+    fn to_array_clone() {
+        let xs: Vec<String> = vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()];
+        let ys: [String; 4] = xs.to_array_clone::<4>().unwrap();
+        assert_eq!(ys, ["a", "b", "c", "d"]);
+        assert_eq!(xs[..3].to_array_clone::<4>(), None);
+    }
+
+    struct PanicOnClone<'a> {
+        index: usize,
+        panic_at: usize,
+        drops: &'a Cell<usize>,
+    }
+
+    impl<'a> Clone for PanicOnClone<'a> {
+        fn clone(&self) -> Self {
+            if self.index == self.panic_at {
+                panic!("clone of element {}", self.index);
+            }
+            PanicOnClone { index: self.index, panic_at: self.panic_at, drops: self.drops }
+        }
+    }
+
+    impl<'a> Drop for PanicOnClone<'a> {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+        }
+    }
+
+    #[test]
+    fn to_array_clone_panic() {
+        let drops = Cell::new(0);
+        let xs: Vec<PanicOnClone> = (0..4).map(|index| PanicOnClone { index, panic_at: 2, drops: &drops }).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| xs.to_array_clone::<4>()));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 2);
+        drop(xs);
+        assert_eq!(drops.get(), 6);
+    }
+}
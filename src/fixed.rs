@@ -0,0 +1,143 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Wrappers recording in the type that a slice was checked to have `N` elements.
+
+#[cfg(feature="use_std")] use std::ops::{Deref, DerefMut};
+#[cfg(not(feature="use_std"))] use core::ops::{Deref, DerefMut};
+use {slice_as_array, slice_as_array_mut};
+
+/// A reference to an array that was checked out of a slice.
+/// It derefs to `[T; N]` and has the same representation as `&[T; N]`.
+#[repr(transparent)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct FixedArrayRef<'a, T: 'a, const N: usize>(&'a [T; N]);
+
+impl<'a, T, const N: usize> FixedArrayRef<'a, T, N> {
+    /// Returns `None` if the slice does not have exactly `N` elements.
+    #[inline]
+    #[track_caller]
+    #[must_use = "converting to an array produces a value that should be used"]
+    pub fn new(slice: &'a [T]) -> Option<Self> {
+        slice_as_array(slice).map(FixedArrayRef)
+    }
+
+    /// The underlying array reference, for the wrapper's whole lifetime.
+    #[inline]
+    pub fn into_inner(self) -> &'a [T; N] {
+        self.0
+    }
+}
+
+impl<'a, T, const N: usize> Clone for FixedArrayRef<'a, T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T, const N: usize> Copy for FixedArrayRef<'a, T, N> {}
+
+impl<'a, T, const N: usize> From<&'a [T; N]> for FixedArrayRef<'a, T, N> {
+    fn from(array: &'a [T; N]) -> Self {
+        FixedArrayRef(array)
+    }
+}
+
+impl<'a, T, const N: usize> Deref for FixedArrayRef<'a, T, N> {
+    type Target = [T; N];
+
+    fn deref(&self) -> &[T; N] {
+        self.0
+    }
+}
+
+/// A mutable reference to an array that was checked out of a slice.
+/// It derefs to `[T; N]` and has the same representation as `&mut [T; N]`.
+#[repr(transparent)]
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct FixedArrayMut<'a, T: 'a, const N: usize>(&'a mut [T; N]);
+
+impl<'a, T, const N: usize> FixedArrayMut<'a, T, N> {
+    /// Returns `None` if the slice does not have exactly `N` elements.
+    #[inline]
+    #[track_caller]
+    #[must_use = "converting to an array produces a value that should be used"]
+    pub fn new(slice: &'a mut [T]) -> Option<Self> {
+        slice_as_array_mut(slice).map(FixedArrayMut)
+    }
+
+    /// The underlying array reference, for the wrapper's whole lifetime.
+    #[inline]
+    pub fn into_inner(self) -> &'a mut [T; N] {
+        self.0
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a mut [T; N]> for FixedArrayMut<'a, T, N> {
+    fn from(array: &'a mut [T; N]) -> Self {
+        FixedArrayMut(array)
+    }
+}
+
+impl<'a, T, const N: usize> Deref for FixedArrayMut<'a, T, N> {
+    type Target = [T; N];
+
+    fn deref(&self) -> &[T; N] {
+        self.0
+    }
+}
+
+impl<'a, T, const N: usize> DerefMut for FixedArrayMut<'a, T, N> {
+    fn deref_mut(&mut self) -> &mut [T; N] {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::mem::size_of;
+    use super::{FixedArrayRef, FixedArrayMut};
+
+    struct NotClone(u8);
+
+    #[test]
+    fn fixed_ref() {
+        let xs = [1u32, 2, 3];
+        let fixed: FixedArrayRef<u32, 3> = FixedArrayRef::new(&xs).unwrap();
+        let copy = fixed;
+        assert_eq!(fixed.len(), 3);
+        assert_eq!(copy.into_inner(), &[1, 2, 3]);
+        assert_eq!(FixedArrayRef::<u32, 2>::new(&xs), None);
+        assert_eq!(FixedArrayRef::from(&xs), fixed);
+        // Copying the wrapper does not need the elements to be `Clone`.
+        let items = [NotClone(7)];
+        let item = FixedArrayRef::<_, 1>::new(&items).unwrap();
+        let again = item;
+        assert_eq!((item[0].0, again[0].0), (7, 7));
+    }
+
+    #[test]
+    fn fixed_mut() {
+        let mut xs = [1u32, 2, 3];
+        {
+            let mut fixed = FixedArrayMut::<_, 3>::new(&mut xs).unwrap();
+            fixed[0] = 10;
+            fixed.reverse();
+            fixed.into_inner()[0] += 1;
+        }
+        assert_eq!(xs, [4, 2, 10]);
+        assert!(FixedArrayMut::<u32, 4>::new(&mut xs).is_none());
+    }
+
+    #[test]
+    fn zero_cost() {
+        assert_eq!(size_of::<FixedArrayRef<u64, 4>>(), size_of::<&[u64; 4]>());
+        assert_eq!(size_of::<Option<FixedArrayMut<u64, 4>>>(), size_of::<&mut [u64; 4]>());
+    }
+}
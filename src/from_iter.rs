@@ -0,0 +1,180 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions that collect an iterator into an array.
+
+use {check, report_length_mismatch, report_mismatch, Mismatch, SafeArrayInitialization, SliceLengthError, TryArrayError, MapArrayError};
+
+/// Collect the first `N` items of an iterator into an array.
+/// Returns `None` if the iterator yields fewer than `N` items, dropping those
+/// it did yield. `next` is called at most `N` times, so any further items are
+/// left in the iterator.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn array_from_iter<T, I: IntoIterator<Item = T>, const N: usize>(iter: I) -> Option<[T; N]> {
+    let mut iter = iter.into_iter();
+    let mut init = SafeArrayInitialization::new();
+    while init.count < N {
+        match iter.next() {
+            Some(val) => init.push(val),
+            None => {
+                report_length_mismatch::<T>(N, init.count);
+                return None;
+            }
+        }
+    }
+    Some(init.finish())
+}
+
+/// Collect an iterator of exactly `N` items into an array.
+/// Returns `None` if the iterator yields fewer or more than `N` items; `next`
+/// is called at most `N + 1` times to find out.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn array_from_iter_exact<T, I: IntoIterator<Item = T>, const N: usize>(iter: I) -> Option<[T; N]> {
+    let mut iter = iter.into_iter();
+    let array = array_from_iter(&mut iter)?;
+    match iter.next() {
+        Some(_) => {
+            // Only one item past the end is looked at, so how many more is unknown.
+            report_mismatch::<T>(N, Mismatch::MoreThan);
+            None
+        }
+        None => Some(array),
+    }
+}
+
+/// Collect the first `N` values from an iterator of `Result`s into an array.
+/// Stops at the first error, returning it as `TryArrayError::Item`, or
+/// returns `TryArrayError::Length` if the iterator yields fewer than `N`
+/// items. In both cases the values collected so far are dropped.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn try_array_from_iter<T, E, I: IntoIterator<Item = Result<T, E>>, const N: usize>(iter: I) -> Result<[T; N], TryArrayError<E>> {
+    let mut iter = iter.into_iter();
+    let mut init = SafeArrayInitialization::new();
+    while init.count < N {
+        match iter.next() {
+            Some(Ok(val)) => init.push(val),
+            Some(Err(err)) => return Err(TryArrayError::Item(err)),
+            None => {
+                report_length_mismatch::<T>(N, init.count);
+                return Err(TryArrayError::Length(SliceLengthError { expected: N, actual: init.count }));
+            }
+        }
+    }
+    Ok(init.finish())
+}
+
+/// Convert a slice to an array by mapping each element with `f`.
+/// Returns `MapArrayError::Length` without calling `f` if the slice does not
+/// have exactly `N` elements. Stops at the first error from `f`, dropping the
+/// values mapped so far, and returns it as `MapArrayError::Item`.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_map_to_array<T, U, E, F: FnMut(&T) -> Result<U, E>, const N: usize>(slice: &[T], f: F) -> Result<[U; N], MapArrayError<E>> {
+    if !check::has_len(slice, N) {
+        report_length_mismatch::<T>(N, slice.len());
+        return Err(TryArrayError::Length(SliceLengthError { expected: N, actual: slice.len() }));
+    }
+    // The length is right, so the only errors left are those from `f`.
+    try_array_from_iter(slice.iter().map(f))
+}
+
+/// Collect the first items of an iterator into an array.
+/// `collect_array!(iterator, array_length) -> Option<[item_type; array_length]>`
+#[macro_export]
+macro_rules! collect_array {
+    ($iter:expr, $len:expr ) => {{
+        $crate::array_from_iter::<_, _, { $len }>($iter)
+    }}
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use super::{array_from_iter, array_from_iter_exact, try_array_from_iter, slice_map_to_array};
+    use {SliceLengthError, TryArrayError, MapArrayError};
+
+    #[test]
+    fn from_iter() {
+        assert_eq!(collect_array!(1..4, 3), Some([1, 2, 3]));
+        assert_eq!(collect_array!(1..3, 3), None);
+    }
+
+    #[test]
+    fn from_iter_leaves_extras() {
+        let mut iter = 1..10;
+        let array: [u32; 3] = array_from_iter(&mut iter).unwrap();
+        assert_eq!(array, [1, 2, 3]);
+        assert_eq!(iter.next(), Some(4));
+    }
+
+    #[test]
+    fn from_iter_exact() {
+        assert_eq!(array_from_iter_exact::<_, _, 3>(1..4), Some([1, 2, 3]));
+        assert_eq!(array_from_iter_exact::<_, _, 3>(1..5), None);
+        assert_eq!(array_from_iter_exact::<_, _, 3>(1..3), None);
+    }
+
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl<'a> Drop for DropCounter<'a> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn from_iter_short_drops() {
+        let drops = Cell::new(0);
+        let nexts = Cell::new(0);
+        let iter = (0..2).map(|_| { nexts.set(nexts.get() + 1); DropCounter(&drops) });
+        assert!(array_from_iter::<_, _, 4>(iter).is_none());
+        assert_eq!((nexts.get(), drops.get()), (2, 2));
+    }
+
+    #[test]
+    fn try_from_iter() {
+        let parsed = "1 2 3".split(' ').map(|s| s.parse::<u8>());
+        assert_eq!(try_array_from_iter(parsed), Ok([1, 2, 3]));
+        let parsed = "1 x 3".split(' ').map(|s| s.parse::<u8>());
+        assert!(matches!(try_array_from_iter::<_, _, _, 3>(parsed), Err(TryArrayError::Item(_))));
+        let parsed = "1 2".split(' ').map(|s| s.parse::<u8>());
+        let short = TryArrayError::Length(SliceLengthError { expected: 3, actual: 2 });
+        assert_eq!(try_array_from_iter::<_, _, _, 3>(parsed), Err(short));
+    }
+
+    #[test]
+    fn try_from_iter_error_drops() {
+        let drops = Cell::new(0);
+        let items = (0..3).map(|i| if i < 2 { Ok(DropCounter(&drops)) } else { Err(i) });
+        assert!(matches!(try_array_from_iter::<_, _, _, 4>(items), Err(TryArrayError::Item(2))));
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn map_to_array() {
+        let words = ["1", "2", "3"];
+        assert_eq!(slice_map_to_array(&words, |s| s.parse::<u32>()), Ok([1, 2, 3]));
+        assert!(matches!(slice_map_to_array::<_, u32, _, _, 3>(&["1", "x", "3"], |s| s.parse()), Err(MapArrayError::Item(_))));
+        let mut calls = 0;
+        let long = slice_map_to_array::<_, u32, _, _, 2>(&words, |s| { calls += 1; s.parse() });
+        assert_eq!(long, Err(MapArrayError::Length(SliceLengthError { expected: 2, actual: 3 })));
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn map_to_array_error_drops() {
+        let drops = Cell::new(0);
+        let mapped = slice_map_to_array::<_, _, _, _, 4>(&[0, 1, 2, 3], |&i| if i < 2 { Ok(DropCounter(&drops)) } else { Err(i) });
+        assert!(matches!(mapped, Err(MapArrayError::Item(2))));
+        assert_eq!(drops.get(), 2);
+    }
+}
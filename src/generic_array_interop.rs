@@ -0,0 +1,65 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions from `generic_array::GenericArray`, behind the `generic-array` feature.
+
+#[cfg(feature="use_std")] use std::mem::ManuallyDrop;
+#[cfg(not(feature="use_std"))] use core::mem::ManuallyDrop;
+use generic_array::{ArrayLength, GenericArray};
+use {reexport, report_length_mismatch, slice_as_array};
+
+/// Borrow a `GenericArray` as an array.
+/// Returns `None` if its length `U` is not `N`; the two kinds of length
+/// cannot be compared at compile time.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn genericarray_as_array<T, U: ArrayLength, const N: usize>(array: &GenericArray<T, U>) -> Option<&[T; N]> {
+    slice_as_array(array.as_slice())
+}
+
+/// Move the elements of a `GenericArray` into an array without cloning them.
+/// Returns the `GenericArray` unchanged if its length `U` is not `N`.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn genericarray_into_array<T, U: ArrayLength, const N: usize>(array: GenericArray<T, U>) -> Result<[T; N], GenericArray<T, U>> {
+    if U::USIZE != N {
+        report_length_mismatch::<T>(N, U::USIZE);
+        return Err(array);
+    }
+    // A `GenericArray` of length `N` is laid out just like `[T; N]`.
+    let array = ManuallyDrop::new(array);
+    Ok(unsafe { reexport::ptr_read(&*array as *const GenericArray<T, U> as *const [T; N]) })
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+    use generic_array::GenericArray;
+    use generic_array::typenum::{U2, U3};
+    use super::{genericarray_as_array, genericarray_into_array};
+
+    #[test]
+    fn as_array() {
+        let xs: GenericArray<u8, U3> = GenericArray::from_array([1, 2, 3]);
+        assert_eq!(genericarray_as_array(&xs), Some(&[1, 2, 3]));
+        assert_eq!(genericarray_as_array::<_, _, 2>(&xs), None);
+    }
+
+    #[test]
+    fn into_array() {
+        let shared = Rc::new(5);
+        let xs: GenericArray<Rc<u32>, U2> = GenericArray::from_array([shared.clone(), shared.clone()]);
+        let xs = genericarray_into_array::<_, _, 3>(xs).unwrap_err();
+        let array: [Rc<u32>; 2] = genericarray_into_array(xs).unwrap();
+        assert_eq!(Rc::strong_count(&shared), 3);
+        drop(array);
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+}
@@ -0,0 +1,410 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions involving heap-allocated collections, behind the `alloc` feature.
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use {check, reexport, report_length_mismatch, report_mismatch, slice_to_array_clone, Mismatch};
+
+/// Move the elements of a `Vec` into an array without cloning them.
+/// Returns the `Vec` unchanged if it does not have exactly `N` elements.
+/// The `Vec`'s allocation is freed, as the array lives on the stack.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn vec_into_array<T, const N: usize>(mut vec: Vec<T>) -> Result<[T; N], Vec<T>> {
+    if !check::has_len(&vec, N) {
+        report_length_mismatch::<T>(N, vec.len());
+        return Err(vec);
+    }
+    let mut array = reexport::MaybeUninit::<[T; N]>::uninit();
+    unsafe {
+        // The elements now belong to `array`, so `vec` must only free its buffer.
+        vec.set_len(0);
+        reexport::copy_nonoverlapping(vec.as_ptr(), array.as_mut_ptr() as *mut T, N);
+        Ok(array.assume_init())
+    }
+}
+
+/// Move the elements of a `Vec` into an array without cloning them.
+/// `vec_into_array!(vec, [element_type; array_length]) -> Result<[element_type; array_length], Vec<element_type>>`
+#[macro_export]
+macro_rules! vec_into_array {
+    ($vec:expr, [$t:ty ; $len:expr] ) => {{
+        $crate::vec_into_array::<$t, { $len }>($vec)
+    }}
+}
+
+/// Move the elements of a `Vec` into an array, leaving the `Vec` empty but
+/// keeping its allocation for reuse.
+/// Returns `None` and leaves the `Vec` untouched if it does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn take_vec_into_array<T, const N: usize>(vec: &mut Vec<T>) -> Option<[T; N]> {
+    if !check::has_len(vec, N) {
+        report_length_mismatch::<T>(N, vec.len());
+        return None;
+    }
+    let mut array = reexport::MaybeUninit::<[T; N]>::uninit();
+    unsafe {
+        // The elements now belong to `array`, so `vec` must not drop them again.
+        vec.set_len(0);
+        reexport::copy_nonoverlapping(vec.as_ptr(), array.as_mut_ptr() as *mut T, N);
+        Some(array.assume_init())
+    }
+}
+
+/// Move the elements of a `Vec` into an array, leaving the `Vec` empty.
+/// `take_vec_into_array!(&mut vec, [element_type; array_length]) -> Option<[element_type; array_length]>`
+#[macro_export]
+macro_rules! take_vec_into_array {
+    ($vec:expr, [$t:ty ; $len:expr] ) => {{
+        $crate::take_vec_into_array::<$t, { $len }>($vec)
+    }}
+}
+
+/// Move the last `N` elements of a `Vec` into an array, leaving the rest of
+/// the `Vec` in place, like popping a fixed-size record off a stack.
+/// Returns `None` and leaves the `Vec` untouched if it has fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn vec_split_off_array<T, const N: usize>(vec: &mut Vec<T>) -> Option<[T; N]> {
+    if !check::has_at_least(vec, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(vec.len()));
+        return None;
+    }
+    let rest = vec.len() - N;
+    let mut array = reexport::MaybeUninit::<[T; N]>::uninit();
+    unsafe {
+        // The last `N` elements now belong to `array`, so `vec` must not drop them again.
+        vec.set_len(rest);
+        reexport::copy_nonoverlapping(vec.as_ptr().add(rest), array.as_mut_ptr() as *mut T, N);
+        Some(array.assume_init())
+    }
+}
+
+/// Move the last elements of a `Vec` into an array, keeping the others in the `Vec`.
+/// `vec_split_off_array!(&mut vec, [element_type; array_length]) -> Option<[element_type; array_length]>`
+#[macro_export]
+macro_rules! vec_split_off_array {
+    ($vec:expr, [$t:ty ; $len:expr] ) => {{
+        $crate::vec_split_off_array::<$t, { $len }>($vec)
+    }}
+}
+
+/// Reinterpret a boxed slice as a boxed array, reusing its allocation.
+/// Returns the boxed slice unchanged if it does not have exactly `N` elements.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn box_slice_into_array<T, const N: usize>(boxed: Box<[T]>) -> Result<Box<[T; N]>, Box<[T]>> {
+    if !check::has_len(&boxed, N) {
+        report_length_mismatch::<T>(N, boxed.len());
+        return Err(boxed);
+    }
+    // Dropping the length from the fat pointer is fine, as `[T; N]` has the
+    // same layout as a `[T]` of length `N`.
+    let ptr = Box::into_raw(boxed) as *mut [T; N];
+    Ok(unsafe { Box::from_raw(ptr) })
+}
+
+/// Reinterpret a boxed slice as a boxed array, reusing its allocation.
+/// `box_slice_into_array!(boxed_slice, [element_type; array_length]) -> Result<Box<[element_type; array_length]>, Box<[element_type]>>`
+#[macro_export]
+macro_rules! box_slice_into_array {
+    ($boxed:expr, [$t:ty ; $len:expr] ) => {{
+        $crate::box_slice_into_array::<$t, { $len }>($boxed)
+    }}
+}
+
+/// Reinterpret a reference-counted slice as a reference-counted array,
+/// reusing its allocation and keeping its reference counts.
+/// Returns the `Rc` unchanged if it does not have exactly `N` elements.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn rc_slice_into_array<T, const N: usize>(rc: Rc<[T]>) -> Result<Rc<[T; N]>, Rc<[T]>> {
+    if !check::has_len(&rc, N) {
+        report_length_mismatch::<T>(N, rc.len());
+        return Err(rc);
+    }
+    let ptr = Rc::into_raw(rc) as *const [T; N];
+    Ok(unsafe { Rc::from_raw(ptr) })
+}
+
+/// Reinterpret a reference-counted slice as a reference-counted array.
+/// `rc_slice_into_array!(rc_slice, [element_type; array_length]) -> Result<Rc<[element_type; array_length]>, Rc<[element_type]>>`
+#[macro_export]
+macro_rules! rc_slice_into_array {
+    ($rc:expr, [$t:ty ; $len:expr] ) => {{
+        $crate::rc_slice_into_array::<$t, { $len }>($rc)
+    }}
+}
+
+/// Reinterpret an atomically reference-counted slice as an array, reusing
+/// its allocation and keeping its reference counts.
+/// Returns the `Arc` unchanged if it does not have exactly `N` elements.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn arc_slice_into_array<T, const N: usize>(arc: Arc<[T]>) -> Result<Arc<[T; N]>, Arc<[T]>> {
+    if !check::has_len(&arc, N) {
+        report_length_mismatch::<T>(N, arc.len());
+        return Err(arc);
+    }
+    let ptr = Arc::into_raw(arc) as *const [T; N];
+    Ok(unsafe { Arc::from_raw(ptr) })
+}
+
+/// Reinterpret an atomically reference-counted slice as an array.
+/// `arc_slice_into_array!(arc_slice, [element_type; array_length]) -> Result<Arc<[element_type; array_length]>, Arc<[element_type]>>`
+#[macro_export]
+macro_rules! arc_slice_into_array {
+    ($arc:expr, [$t:ty ; $len:expr] ) => {{
+        $crate::arc_slice_into_array::<$t, { $len }>($arc)
+    }}
+}
+
+/// Convert a `Cow` slice to an array, cloning the elements only if they are
+/// borrowed. An owned `Vec` is moved out of instead, as by `vec_into_array`.
+/// Returns `None` if the slice does not have exactly `N` elements.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn cow_slice_as_array<T: Clone, const N: usize>(cow: Cow<[T]>) -> Option<[T; N]> {
+    match cow {
+        Cow::Borrowed(slice) => slice_to_array_clone(slice),
+        Cow::Owned(vec) => vec_into_array(vec).ok(),
+    }
+}
+
+/// Convert a `Cow` slice to an array, only cloning borrowed elements.
+/// `cow_slice_as_array!(cow, [element_type; array_length]) -> Option<[element_type; array_length]>`
+#[macro_export]
+macro_rules! cow_slice_as_array {
+    ($cow:expr, [$t:ty ; $len:expr] ) => {{
+        $crate::cow_slice_as_array::<$t, { $len }>($cow)
+    }}
+}
+
+/// Move the elements of an array into a new `Vec` without cloning them.
+/// This allocates once, unless `N` is zero or `T` is zero-sized.
+#[inline]
+pub fn array_into_vec<T, const N: usize>(array: [T; N]) -> Vec<T> {
+    Vec::from(array)
+}
+
+/// Clone the elements of an array into a new `Vec`.
+#[inline]
+pub fn array_as_vec<T: Clone, const N: usize>(array: &[T; N]) -> Vec<T> {
+    array.to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[test]
+    fn vec_into_array() {
+        let xs = vec![1, 2, 3];
+        assert_eq!(vec_into_array!(xs, [u32; 3]), Ok([1, 2, 3]));
+    }
+
+    #[test]
+    fn vec_into_array_wrong_length() {
+        let xs = vec![1, 2, 3];
+        assert_eq!(vec_into_array!(xs, [u32; 4]), Err(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn vec_into_array_moves() {
+        // `Rc` is not `Copy`, and its count shows whether anything was cloned or dropped.
+        let shared = Rc::new(5);
+        let xs = vec![shared.clone(), shared.clone()];
+        let array: [Rc<u32>; 2] = vec_into_array!(xs, [Rc<u32>; 2]).unwrap();
+        assert_eq!(Rc::strong_count(&shared), 3);
+        drop(array);
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn box_slice_into_array() {
+        let xs: Box<[u32]> = vec![1, 2, 3].into_boxed_slice();
+        let ptr = xs.as_ptr();
+        let array: Box<[u32; 3]> = box_slice_into_array!(xs, [u32; 3]).unwrap();
+        assert_eq!(*array, [1, 2, 3]);
+        assert_eq!(array.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn box_slice_into_array_wrong_length() {
+        let shared = Rc::new(5);
+        let xs: Box<[Rc<u32>]> = vec![shared.clone(), shared.clone()].into_boxed_slice();
+        let xs = box_slice_into_array!(xs, [Rc<u32>; 3]).unwrap_err();
+        assert_eq!(xs.len(), 2);
+        assert_eq!(Rc::strong_count(&shared), 3);
+    }
+
+    #[test]
+    fn box_slice_into_array_moves() {
+        let shared = Rc::new(5);
+        let xs: Box<[Rc<u32>]> = vec![shared.clone(), shared.clone()].into_boxed_slice();
+        let array = box_slice_into_array!(xs, [Rc<u32>; 2]).unwrap();
+        assert_eq!(Rc::strong_count(&shared), 3);
+        drop(array);
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn rc_slice_into_array() {
+        let xs: Rc<[u32]> = Rc::from(vec![1, 2, 3]);
+        let other = xs.clone();
+        let array: Rc<[u32; 3]> = rc_slice_into_array!(xs, [u32; 3]).unwrap();
+        assert_eq!(*array, [1, 2, 3]);
+        assert_eq!(array.as_ptr(), other.as_ptr());
+        assert_eq!(Rc::strong_count(&other), 2);
+    }
+
+    #[test]
+    fn rc_slice_into_array_wrong_length() {
+        let shared = Rc::new(5);
+        let xs: Rc<[Rc<u32>]> = Rc::from(vec![shared.clone(), shared.clone()]);
+        let xs = rc_slice_into_array!(xs, [Rc<u32>; 3]).unwrap_err();
+        assert_eq!(xs.len(), 2);
+        assert_eq!(Rc::strong_count(&shared), 3);
+    }
+
+    #[test]
+    fn arc_slice_into_array() {
+        let xs: Arc<[u32]> = Arc::from(vec![1, 2, 3]);
+        let other = xs.clone();
+        let array: Arc<[u32; 3]> = arc_slice_into_array!(xs, [u32; 3]).unwrap();
+        assert_eq!(*array, [1, 2, 3]);
+        assert_eq!(Arc::strong_count(&other), 2);
+        drop(other);
+        assert_eq!(Arc::strong_count(&array), 1);
+    }
+
+    #[test]
+    fn arc_slice_into_array_wrong_length() {
+        let shared = Arc::new(5);
+        let xs: Arc<[Arc<u32>]> = Arc::from(vec![shared.clone(), shared.clone()]);
+        let xs = arc_slice_into_array!(xs, [Arc<u32>; 3]).unwrap_err();
+        assert_eq!(xs.len(), 2);
+        assert_eq!(Arc::strong_count(&shared), 3);
+    }
+
+    #[test]
+    fn array_into_vec() {
+        let shared = Rc::new(5);
+        let xs = super::array_into_vec([shared.clone(), shared.clone()]);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(Rc::strong_count(&shared), 3);
+        assert_eq!(vec_into_array!(xs, [Rc<u32>; 2]).map(|a| *a[0]), Ok(5));
+    }
+
+    #[test]
+    fn array_as_vec() {
+        let xs: [u32; 3] = [1, 2, 3];
+        assert_eq!(super::array_as_vec(&xs), vec![1, 2, 3]);
+    }
+
+    // Counts clones and drops.
+    struct Tracked<'a> {
+        clones: &'a Cell<usize>,
+        drops: &'a Cell<usize>,
+    }
+
+    impl<'a> Clone for Tracked<'a> {
+        fn clone(&self) -> Self {
+            self.clones.set(self.clones.get() + 1);
+            Tracked { clones: self.clones, drops: self.drops }
+        }
+    }
+
+    impl<'a> Drop for Tracked<'a> {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+        }
+    }
+
+    #[test]
+    fn cow_borrowed_clones() {
+        let (clones, drops) = (Cell::new(0), Cell::new(0));
+        let xs = [Tracked { clones: &clones, drops: &drops }, Tracked { clones: &clones, drops: &drops }];
+        let array = cow_slice_as_array!(Cow::Borrowed(&xs[..]), [Tracked; 2]).unwrap();
+        assert_eq!((clones.get(), drops.get()), (2, 0));
+        drop(array);
+        assert_eq!(drops.get(), 2);
+        assert!(cow_slice_as_array!(Cow::Borrowed(&xs[..]), [Tracked; 3]).is_none());
+        assert_eq!(clones.get(), 2);
+    }
+
+    #[test]
+    fn cow_owned_moves() {
+        let (clones, drops) = (Cell::new(0), Cell::new(0));
+        let xs = vec![Tracked { clones: &clones, drops: &drops }, Tracked { clones: &clones, drops: &drops }];
+        let array = cow_slice_as_array!(Cow::Owned(xs), [Tracked; 2]).unwrap();
+        assert_eq!((clones.get(), drops.get()), (0, 0));
+        drop(array);
+        assert_eq!(drops.get(), 2);
+        let ys: Cow<[u8]> = Cow::Owned(vec![1, 2, 3]);
+        assert_eq!(cow_slice_as_array!(ys, [u8; 2]), None);
+    }
+
+    #[test]
+    fn take_vec_into_array() {
+        let shared = Rc::new(5);
+        let mut buffer = Vec::with_capacity(8);
+        buffer.push(shared.clone());
+        buffer.push(shared.clone());
+        let ptr = buffer.as_ptr();
+        assert!(take_vec_into_array!(&mut buffer, [Rc<u32>; 3]).is_none());
+        assert_eq!((buffer.len(), Rc::strong_count(&shared)), (2, 3));
+        let array = take_vec_into_array!(&mut buffer, [Rc<u32>; 2]).unwrap();
+        assert!(buffer.is_empty());
+        assert_eq!((buffer.capacity(), buffer.as_ptr()), (8, ptr));
+        assert_eq!(Rc::strong_count(&shared), 3);
+        drop(buffer);
+        assert_eq!(Rc::strong_count(&shared), 3);
+        drop(array);
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn vec_split_off_array() {
+        let shared = Rc::new(5);
+        let mut stack = vec![shared.clone(), shared.clone(), shared.clone()];
+        let ptr = stack.as_ptr();
+        assert!(vec_split_off_array!(&mut stack, [Rc<u32>; 4]).is_none());
+        assert_eq!(stack.len(), 3);
+        let record = vec_split_off_array!(&mut stack, [Rc<u32>; 2]).unwrap();
+        assert_eq!((stack.len(), stack.as_ptr()), (1, ptr));
+        assert!(Rc::ptr_eq(&stack[0], &shared) && Rc::ptr_eq(&record[1], &shared));
+        assert_eq!(Rc::strong_count(&shared), 4);
+        drop(record);
+        assert_eq!(Rc::strong_count(&shared), 2);
+        let last: [Rc<u32>; 1] = vec_split_off_array!(&mut stack, [Rc<u32>; 1]).unwrap();
+        assert!(stack.is_empty());
+        assert_eq!(vec_split_off_array!(&mut stack, [Rc<u32>; 0]), Some([]));
+        drop(last);
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn vec_split_off_array_keeps_order() {
+        let mut xs = vec![1, 2, 3, 4, 5];
+        assert_eq!(vec_split_off_array!(&mut xs, [u32; 2]), Some([4, 5]));
+        assert_eq!(xs, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions from `heapless::Vec`, behind the `heapless` feature.
+
+use heapless::Vec;
+use {check, reexport, report_length_mismatch};
+
+/// Move the elements of a `heapless::Vec` into an array without cloning them.
+/// Returns the vector unchanged if it does not have exactly `N` elements, so
+/// any `N` up to the capacity `C` can succeed.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn heapless_into_array<T, const C: usize, const N: usize>(mut vec: Vec<T, C>) -> Result<[T; N], Vec<T, C>> {
+    if !check::has_len(&vec, N) {
+        report_length_mismatch::<T>(N, vec.len());
+        return Err(vec);
+    }
+    let mut array = reexport::MaybeUninit::<[T; N]>::uninit();
+    unsafe {
+        // The elements now belong to `array`, so `vec` must not drop them.
+        vec.set_len(0);
+        reexport::copy_nonoverlapping(vec.as_ptr(), array.as_mut_ptr() as *mut T, N);
+        Ok(array.assume_init())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+    use heapless::Vec;
+    use super::heapless_into_array;
+
+    #[test]
+    fn into_array() {
+        let xs: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(heapless_into_array(xs), Ok([1, 2, 3]));
+    }
+
+    #[test]
+    fn wrong_length() {
+        let xs: Vec<u8, 8> = Vec::from_slice(&[1, 2, 3]).unwrap();
+        let xs = heapless_into_array::<_, 8, 4>(xs).unwrap_err();
+        assert_eq!(&xs[..], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn moves() {
+        let shared = Rc::new(5);
+        let mut xs: Vec<Rc<u32>, 4> = Vec::new();
+        for _ in 0..2 {
+            xs.push(shared.clone()).unwrap();
+        }
+        let array: [Rc<u32>; 2] = heapless_into_array(xs).unwrap();
+        assert_eq!(Rc::strong_count(&shared), 3);
+        drop(array);
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+}
@@ -16,26 +16,60 @@
 //! a slice of length 4, or `None` otherwise.
 //!
 //! `slice_as_array_mut!(ys, [String; 7])` returns `Some(&mut [String; 7])`
-//!  if `ys` was a slice of length 7, or `None` otherwise.
+//! if `ys` was a slice of length 7, or `None` otherwise.
 //!
-//! `slice_to_array_clone!(zs, [String; 4]` returns `Some([String; 4])`
-//! if `zs` was a slice of length 4, or `None otherwise. The passed-in slice
+//! `slice_to_array_clone!(zs, [String; 4])` returns `Some([String; 4])`
+//! if `zs` was a slice of length 4, or `None` otherwise. The passed-in slice
 //! remains intact and its elements are cloned.
 //!
+//! Every macro taking a slice accepts anything implementing `AsRef<[T]>` in
+//! its place: a slice, `&Vec<T>` or `&[T; N]`, or an owned `Vec<T>`,
+//! `Box<[T]>` or array, which is borrowed rather than moved. Those taking a
+//! mutable slice accept `&mut` of anything implementing `AsMut<[T]>`, such as
+//! `&mut Vec<T>`, so an immutable argument is still a type error.
+//!
+//! The length may be a const generic parameter, `[u8; N]`, or an associated
+//! const of a concrete type, `[u8; Header::LEN]`, but not one that depends
+//! on a type parameter, such as `[u8; T::LEN]`.
+//!
+//! Further macros and functions borrow part of a slice, pad a short one,
+//! reshape, reinterpret bytes, collect iterators and convert heap types; each
+//! is described in its own documentation, and most macros have a function of
+//! the same name. `use slice_as_array::prelude::*;` imports all of them
+//! without needing `#[macro_use]`. The `ext` module has the conversions as
+//! methods, `error` the types saying why one failed, and `check` the length
+//! tests they use.
+//!
 //! For most users, stating a dependency on this is simply:
 //!
 //! ```ignore
 //! [dependencies]
 //! slice_as_array "1.1.0"
 //! ```
+//! The minimum supported Rust version is 1.83, declared as `rust-version` in
+//! `Cargo.toml`.
+//!
 //! To support being called from a `#![no_std]` crate, this crate has a feature
-//! named `with_std` that is on by default. A `#![no_std]` crate should use:
+//! named `use_std` that is on by default. A `#![no_std]` crate should use:
 //!
 //! ```ignore
 //! [dependencies]
 //! slice_as_array = { version = "1.1.0", default-features = false }
 //! ```
 //!
+//! The other features are:
+//!
+//! - `alloc`, implied by `use_std`, for the conversions involving `Vec` and
+//!   other heap types.
+//! - `total_api`, for range forms of `slice_as_array_mut!`,
+//!   `slice_to_array_clone!` and `slice_to_array_copy!` that never panic.
+//! - `log` and `tracing`, to emit a debug record naming the caller and the
+//!   reason whenever a conversion fails.
+//! - `arrayvec`, `smallvec`, `heapless`, `tinyvec`, `generic-array` and
+//!   `zerocopy`, for conversions from those crates' types.
+//! - `generic_const_exprs` and `specialization`, which need a nightly
+//!   toolchain.
+//!
 //! Example usage:
 //!
 //! ```ignore
@@ -50,123 +84,776 @@
 //! }
 //!
 //! fn mutate_chunks(xs: &mut [u32; 100]) {
-//!     for chunk in xs.chunks_mut(10).map(|c| slice_as_array_mut!(c, [u32; 10]).unwrap() ) {
+//!     for chunk in array_chunks_mut!(xs, 10) {
 //!         mutate_chunk(chunk)
 //!     }
 //! }
 //! ```
 //!
 
+#![cfg_attr(not(any(feature="use_std", test)), no_std)]
+#![cfg_attr(any(feature="generic_const_exprs", feature="specialization"), allow(incomplete_features))]
+#![cfg_attr(feature="generic_const_exprs", feature(generic_const_exprs))]
+#![cfg_attr(feature="specialization", feature(specialization))]
+
+// Unit tests always link std, but the macros still expand to `::core` paths.
+#[cfg(all(test, not(feature="use_std")))]
+extern crate core;
+
+#[cfg(feature="alloc")]
+extern crate alloc;
+
+#[cfg(feature="arrayvec")]
+extern crate arrayvec;
+
+#[cfg(feature="generic-array")]
+extern crate generic_array;
+
+#[cfg(feature="heapless")]
+extern crate heapless;
+
+#[cfg(feature="log")]
+extern crate log;
+
+#[cfg(feature="smallvec")]
+extern crate smallvec;
+
+#[cfg(feature="tinyvec")]
+extern crate tinyvec;
+
+#[cfg(feature="tracing")]
+extern crate tracing;
+
+#[cfg(feature="zerocopy")]
+extern crate zerocopy;
+
+#[cfg(feature="arrayvec")]
+mod arrayvec_interop;
+mod builder;
+mod bytes;
+pub mod check;
+mod chunks;
+pub mod error;
+mod expect;
+pub mod ext;
+mod fixed;
+mod from_iter;
+#[cfg(feature="generic-array")]
+mod generic_array_interop;
+#[cfg(feature="alloc")]
+mod heap;
+#[cfg(feature="heapless")]
+mod heapless_interop;
+mod owned;
+mod pad;
+mod pin;
+pub mod prelude;
+#[cfg(feature="use_std")]
+mod read;
+mod report;
+mod reshape;
+#[cfg(feature="smallvec")]
+mod smallvec_interop;
+mod split;
+#[cfg(feature="tinyvec")]
+mod tinyvec_interop;
+mod transmute;
+mod try_from;
+mod tuple;
+mod uninit;
+#[cfg(feature="zerocopy")]
+mod zerocopy_interop;
+
+#[cfg(feature="arrayvec")]
+pub use arrayvec_interop::{arrayvec_into_array, arrayvec_as_array};
+pub use builder::ArrayBuilder;
+pub use bytes::{bytes_as_array, Pod};
+pub use bytes::{slice_to_int_array_le, slice_to_int_array_be, Integer};
+pub use bytes::{str_as_byte_array, byte_array_as_str};
+pub use chunks::{array_chunks_ref, ArrayChunks};
+pub use chunks::{exact_array_chunks, ExactArrayChunks};
+pub use chunks::{rarray_chunks, RArrayChunks};
+pub use chunks::{array_chunks_mut, ArrayChunksMut};
+pub use chunks::{array_windows, ArrayWindows};
+pub use error::{SliceLengthError, ConversionError, TryArrayError, MapArrayError};
+pub use ext::SliceAsArray;
+pub use fixed::{FixedArrayRef, FixedArrayMut};
+pub use expect::{slice_as_array_expect, slice_as_array_mut_expect, slice_to_array_clone_expect};
+pub use from_iter::{array_from_iter, array_from_iter_exact, try_array_from_iter, slice_map_to_array};
+#[cfg(feature="generic-array")]
+pub use generic_array_interop::{genericarray_as_array, genericarray_into_array};
+#[cfg(feature="smallvec")]
+pub use smallvec_interop::smallvec_into_array;
+pub use split::{slice_as_array_prefix, slice_as_array_prefix_mut};
+pub use split::{slice_as_array_suffix, slice_as_array_suffix_mut};
+pub use split::{slice_split_array, slice_split_array_mut, slice_clone_split};
+pub use split::{slice_rsplit_array, slice_rsplit_array_mut};
+pub use split::{slice_as_array_at, slice_as_array_at_mut};
+pub use split::{slice_as_array_at_saturating, slice_as_array_at_saturating_mut};
+pub use split::{slice_as_array_range, slice_two_arrays_mut};
+pub use split::{slice_split_mid_array, slice_split_mid_array_mut};
+pub use split::{zip_slices_as_arrays, zip_slices_as_arrays_mut, strip_prefix_as_array};
+pub use owned::{concat_arrays, split_array_owned};
+#[cfg(feature="generic_const_exprs")]
+pub use owned::concat_arrays_exact;
+pub use pad::{slice_to_array_default, slice_to_array_default_fallible};
+pub use pad::{slice_to_array_or, slice_to_array_with, array_from_slice_or_fn};
+pub use pin::{pin_slice_as_array, pin_slice_as_array_mut};
+pub use reshape::{slice_as_2d_array, slice_as_2d_array_mut, slice_as_2d_array_rows_mut};
+pub use reshape::{array_flatten, array_flatten_mut, row_as_array, row_as_array_mut};
+pub use reshape::{slice_as_array_slice, slice_as_array_slice_mut};
+pub use reshape::{array_reshape, array_reshape_mut, array_reshape_owned};
+#[cfg(feature="generic_const_exprs")]
+pub use reshape::{array_flatten_to_array, array_flatten_to_array_mut};
+#[cfg(feature="tinyvec")]
+pub use tinyvec_interop::tinyvec_arrayvec_into_array;
+#[cfg(all(feature="tinyvec", feature="alloc"))]
+pub use tinyvec_interop::tinyvec_into_array;
+pub use transmute::{transmute_array_ref, transmute_array_mut, SameLayout};
+pub use try_from::{try_from_slice, try_from_slice_mut, try_from_slice_cloned};
+pub use tuple::{array_as_tuple, tuple_as_array, ArrayTuple, TupleArray};
+pub use uninit::{uninit_slice_as_array_mut, assume_init_array, assume_init_array_mut};
+#[cfg(feature="zerocopy")]
+pub use zerocopy_interop::from_bytes_array;
+#[cfg(feature="use_std")]
+pub use read::read_exact_array;
+#[cfg(feature="alloc")]
+pub use heap::{vec_into_array, box_slice_into_array, rc_slice_into_array, arc_slice_into_array};
+#[cfg(feature="alloc")]
+pub use heap::{array_into_vec, array_as_vec, cow_slice_as_array, take_vec_into_array};
+#[cfg(feature="alloc")]
+pub use heap::vec_split_off_array;
+#[cfg(feature="heapless")]
+pub use heapless_interop::heapless_into_array;
+
+use report::{report_length_mismatch, report_mismatch, Mismatch};
+
 #[doc(hidden)]
 pub mod reexport {
-    #[inline] pub fn clone<T: Clone>(source: T) -> T { source.clone() }
-    #[inline] pub unsafe fn ptr_write<T>(dst: *mut T, src: T) { ::std::ptr::write(dst, src) }
-    #[inline] pub unsafe fn ptr_read<T>(src: *const T) -> T { ::std::ptr::read(src) }
-    #[inline] pub fn forget<T>(t: T) { ::std::mem::forget(t) }
-    #[inline] pub unsafe fn uninitialized<T>() -> T { ::std::mem::uninitialized() }
+    #[cfg(feature="use_std")] use std::{mem, ptr, slice};
+    #[cfg(not(feature="use_std"))] use core::{mem, ptr, slice};
+
+    pub use self::mem::MaybeUninit;
+    #[inline] pub fn clone<T: Clone>(source: &T) -> T { source.clone() }
+    #[inline] pub unsafe fn ptr_write<T>(dst: *mut T, src: T) { ptr::write(dst, src) }
+    #[inline] pub unsafe fn copy_nonoverlapping<T>(src: *const T, dst: *mut T, count: usize) { ptr::copy_nonoverlapping(src, dst, count) }
+    #[inline] pub unsafe fn ptr_read<T>(src: *const T) -> T { ptr::read(src) }
+    #[inline] pub unsafe fn drop_in_place<T>(to_drop: *mut T) { ptr::drop_in_place(to_drop) }
+    #[inline] pub fn forget<T>(t: T) { mem::forget(t) }
+    #[inline] pub unsafe fn slice_from_raw_parts<'a, T>(data: *const T, len: usize) -> &'a [T] { slice::from_raw_parts(data, len) }
+
+    // Every cast to an array reference goes through this, so that a future
+    // reinterpreting conversion with a wrong alignment check is caught in
+    // debug builds. It compiles to nothing in release builds.
+    #[inline] pub fn debug_assert_aligned<A>(ptr: *const A) {
+        debug_assert!(ptr.is_aligned(), "array pointer {:p} is not aligned to {} bytes", ptr, mem::align_of::<A>());
+    }
+
+    // Called with method syntax, so that an owned `Vec` or array argument is
+    // borrowed in place by autoref, while a reference is used as it is. Every
+    // type has it, so that a wrong argument fails the bound of `slice_arg`
+    // below with a readable message, rather than failing method lookup.
+    pub trait SliceArgRef {
+        #[inline] fn slice_arg_ref(&self) -> &Self { self }
+    }
+
+    impl<S: ?Sized> SliceArgRef for S {}
+
+    #[diagnostic::on_unimplemented(message = "`slice_as_array!` expects a slice or an `AsRef<[T]>` value, found `{Self}`")]
+    pub trait IntoSliceArg<T> {
+        fn as_slice_arg(&self) -> &[T];
+    }
+
+    impl<T, S: AsRef<[T]> + ?Sized> IntoSliceArg<T> for S {
+        #[inline] fn as_slice_arg(&self) -> &[T] { self.as_ref() }
+    }
+
+    // The immutable slice argument of every macro goes through this.
+    #[inline] pub fn slice_arg<T, S: IntoSliceArg<T> + ?Sized>(s: &S) -> &[T] { s.as_slice_arg() }
+
+    // The mutable path keeps taking an explicit `&mut`, so an immutable
+    // argument is still a type error; the result borrows the whole `S`.
+    #[inline] pub fn as_mut_slice_arg<T, S: AsMut<[T]> + ?Sized>(s: &mut S) -> &mut [T] { s.as_mut() }
+
+    // Evaluated in an inline `const` block by `slice_as_array_const_len!`.
+    pub const fn assert_const_len(len: usize, invariant: bool, message: &str) {
+        assert!(len != 0, "array length must be non-zero");
+        if !invariant {
+            panic!("{}", message);
+        }
+    }
 }
 
-#[cfg(feature="use_std")]
-#[macro_export]
-#[doc(hidden)]
-macro_rules! slice_as_array_transmute {
-    ($slice:expr) => { ::std::mem::transmute($slice) }
+/// Convert a slice to an array.
+/// Returns `None` if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array<T, const N: usize>(slice: &[T]) -> Option<&[T; N]> {
+    if check::has_len(slice, N) {
+        // A plain pointer cast; the signature ties the array's lifetime to the slice's.
+        let ptr = slice.as_ptr() as *const [T; N];
+        reexport::debug_assert_aligned(ptr);
+        Some( unsafe { &*ptr } )
+    } else {
+        report_length_mismatch::<T>(N, slice.len());
+        None
+    }
 }
 
-#[cfg(not(feature="use_std"))]
-#[macro_export]
-macro_rules! slice_as_array_transmute {
-    ($slice:expr) => { ::core::mem::transmute($slice) }
+/// Convert a slice to an array in a `const` or `static` initializer.
+/// Returns `None` if the slice does not have exactly `N` elements.
+#[inline]
+#[must_use = "converting to an array produces a value that should be used"]
+pub const fn slice_as_array_const<T, const N: usize>(slice: &[T]) -> Option<&[T; N]> {
+    if check::has_len(slice, N) {
+        Some( unsafe { &*(slice.as_ptr() as *const [T; N]) } )
+    } else {
+        None
+    }
+}
+
+/// Convert a slice to an array.
+/// Returns an error recording both lengths if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn try_slice_as_array<T, const N: usize>(slice: &[T]) -> Result<&[T; N], SliceLengthError> {
+    slice_as_array(slice).ok_or(SliceLengthError { expected: N, actual: slice.len() })
+}
+
+struct AssertNonZero<const N: usize>;
+
+impl<const N: usize> AssertNonZero<N> {
+    // Evaluated, and so checked, when the function using it is monomorphized.
+    const HOLDS: () = assert!(N > 0, "array length must be non-zero");
+}
+
+/// Convert a slice to a non-empty array.
+/// Returns `None` if the slice does not have exactly `N` elements, and it is
+/// a compile error for `N` to be 0.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_nonempty_array<T, const N: usize>(slice: &[T]) -> Option<&[T; N]> {
+    let () = AssertNonZero::<N>::HOLDS;
+    slice_as_array(slice)
 }
 
+/// Convert a mutable slice to a mutable array.
+/// Returns `None` if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_mut<T, const N: usize>(slice: &mut [T]) -> Option<&mut [T; N]> {
+    if check::has_len(slice, N) {
+        let ptr = slice.as_mut_ptr() as *mut [T; N];
+        reexport::debug_assert_aligned(ptr);
+        Some( unsafe { &mut *ptr } )
+    } else {
+        report_length_mismatch::<T>(N, slice.len());
+        None
+    }
+}
 
-// In slice_as_array[_mut], the inner function is to set the lifetime of the created array.
+/// Borrow an array as a slice. This is the same as `&array[..]`, but is a
+/// `const fn`.
+#[inline]
+pub const fn array_as_slice<T, const N: usize>(array: &[T; N]) -> &[T] {
+    array
+}
+
+/// Mutably borrow an array as a slice. This is the same as `&mut array[..]`,
+/// but is a `const fn`.
+#[inline]
+pub const fn array_as_mut_slice<T, const N: usize>(array: &mut [T; N]) -> &mut [T] {
+    array
+}
+
+/// Borrow an array as a slice, along with its length `N`, for generic code
+/// that needs the length as a value.
+#[inline]
+pub const fn array_as_slice_with_len<T, const N: usize>(array: &[T; N]) -> (&[T], usize) {
+    (array, N)
+}
+
+// The range arms of the macros other than `slice_as_array!` expand through
+// this, so that they are only usable with the `total_api` feature.
+#[cfg(feature="total_api")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __total_api {
+    ($($body:tt)*) => { $($body)* }
+}
+
+#[cfg(not(feature="total_api"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __total_api {
+    ($($body:tt)*) => { compile_error!("taking a base slice and a range needs the `total_api` feature of slice_as_array") }
+}
 
 /// Convert a slice to an array.
 /// `slice_as_array!(slice, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+/// The element type may be left out, as in `slice_as_array!(slice, array_length)`.
+/// `slice_as_array!(slice, start..end, [element_type; array_length])` converts
+/// just that range, returning `None` instead of panicking if it is out of bounds.
+/// Each bound must be a single token, such as `i` or `(i + 1)`.
 #[macro_export]
 macro_rules! slice_as_array {
     ($slice:expr, [$t:ty ; $len:expr] ) => {{
-        unsafe fn this_transmute(xs: &[$t]) -> &[$t; $len] {
-            slice_as_array_transmute!(xs.as_ptr())
-        }
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array::<$t, { $len }>(s)
+    }};
+    ($slice:expr, $start:tt .. $end:tt, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_range::<$t, { $len }>(s, $start..$end)
+    }};
+    ($slice:expr, $start:tt .. $end:tt ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[_] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_range(s, $start..$end)
+    }};
+    ($slice:expr, $len:expr ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[_] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array::<_, { $len }>(s)
+    }}
+}
 
-        let s: &[$t] = $slice;
-        if s.len() == $len {
-            Some( unsafe { this_transmute(s) } )
-        } else {
-            None
-        }
+/// Convert a slice to an array, reporting the lengths on mismatch.
+/// `try_slice_as_array!(slice, [element_type; array_length]) -> Result<&[element_type; array_length], SliceLengthError>`
+/// The element type may be left out, as in `try_slice_as_array!(slice, array_length)`.
+#[macro_export]
+macro_rules! try_slice_as_array {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::try_slice_as_array::<$t, { $len }>(s)
+    }};
+    ($slice:expr, $len:expr ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[_] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::try_slice_as_array::<_, { $len }>(s)
+    }}
+}
+
+/// Convert a slice to an array whose length must not be 0.
+/// `slice_as_nonempty_array!(slice, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_nonempty_array {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_nonempty_array::<$t, { $len }>(s)
+    }}
+}
+
+/// Convert a slice to an array, rejecting a length of 0, or one that breaks
+/// a further invariant, at compile time in an inline `const` block.
+/// Inline `const` needs Rust 1.79, within the crate's `rust-version` of 1.83.
+/// `slice_as_array_const_len!(slice, array_length) -> Option<&[element_type; array_length]>`
+/// `slice_as_array_const_len!(slice, array_length, const_invariant) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_array_const_len {
+    ($slice:expr, $len:expr ) => {
+        $crate::slice_as_array_const_len!($slice, $len, true)
+    };
+    ($slice:expr, $len:expr, $invariant:expr ) => {{
+        const { $crate::reexport::assert_const_len($len, $invariant, concat!("array length invariant does not hold: ", stringify!($invariant))) };
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[_] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array::<_, { $len }>(s)
+    }}
+}
+
+/// Convert a slice to an array and pass it to `f`, which may fail in turn.
+/// Returns `None` without calling `f` if the slice does not have exactly `N`
+/// elements, so that chained conversions give a flat `Option`.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_then<'a, T, R, F: FnOnce(&'a [T; N]) -> Option<R>, const N: usize>(slice: &'a [T], f: F) -> Option<R> {
+    slice_as_array(slice).and_then(f)
+}
+
+/// Convert a slice to an array and continue with a closure returning an `Option`.
+/// `slice_as_array_then!(slice, [element_type; array_length], |array| expression) -> Option<result_type>`
+#[macro_export]
+macro_rules! slice_as_array_then {
+    ($slice:expr, [$t:ty ; $len:expr], $f:expr ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_then::<$t, _, _, { $len }>(s, $f)
     }}
 }
 
 /// Convert a mutable slice to a mutable array.
 /// `slice_as_array_mut!(mutable_slice, [element_type; array_length]) -> Option<&mut [element_type; array_length]>`
+/// Anything that is `AsMut<[element_type]>`, such as `&mut vec`, may be passed in place of the slice.
+/// The element type may be left out, as in `slice_as_array_mut!(slice, array_length)`.
+/// With the `total_api` feature, `slice_as_array_mut!(slice, start..end, [element_type; array_length])`
+/// converts just that range, returning `None` instead of panicking if it is out of bounds.
 #[macro_export]
 macro_rules! slice_as_array_mut {
     ($slice:expr, [$t:ty ; $len:expr] ) => {{
-        unsafe fn this_transmute(xs: &mut [$t]) -> &mut [$t; $len] {
-            slice_as_array_transmute!(xs.as_mut_ptr())
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_mut::<$t, { $len }>(s)
+    }};
+    ($slice:expr, $start:tt .. $end:tt, [$t:ty ; $len:expr] ) => { $crate::__total_api! {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        match s.get_mut($start..$end) { Some(s) => $crate::slice_as_array_mut::<$t, { $len }>(s), None => None }
+    }} };
+    ($slice:expr, $len:expr ) => {{
+        let s: &mut [_] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_mut::<_, { $len }>(s)
+    }}
+}
+
+// Only the first `count` elements of `array` are initialized. If a clone
+// panics, Drop cleans up exactly those. `count` is only bumped after a write
+// succeeds, and `finish` takes `array` so that Drop then does nothing; so
+// each element is dropped once, and uninitialized slots are never read.
+struct SafeArrayInitialization<T, const N: usize> {
+    array: Option<reexport::MaybeUninit<[T; N]>>,
+    count: usize,
+}
+
+impl<T, const N: usize> SafeArrayInitialization<T, N> {
+    fn new() -> Self {
+        SafeArrayInitialization { array: Some(reexport::MaybeUninit::uninit()), count: 0 }
+    }
+
+    fn push(&mut self, val: T) {
+        assert!(self.count < N);
+        let array_ptr = self.array.as_mut().unwrap().as_mut_ptr() as *mut T;
+        unsafe { reexport::ptr_write(array_ptr.add(self.count), val) };
+        self.count += 1;
+    }
+
+    fn finish(mut self) -> [T; N] {
+        assert_eq!(self.count, N);
+        let array = self.array.take().unwrap();
+        unsafe { array.assume_init() }
+    }
+
+    fn as_slice(&self) -> &[T] {
+        let array_ptr = self.array.as_ref().unwrap().as_ptr() as *const T;
+        // Only the first `count` elements have been written.
+        unsafe { reexport::slice_from_raw_parts(array_ptr, self.count) }
+    }
+
+    fn init_from_slice(mut self, slice: &[T]) -> [T; N] where T: Clone {
+        debug_assert_eq!((self.count, slice.len()), (0, N));
+        for val in slice.iter() {
+            self.push(reexport::clone(val));
         }
+        self.finish()
+    }
+}
 
-        let s: &mut [$t] = $slice;
-        if s.len() == $len {
-            Some( unsafe { this_transmute(s) } )
-        } else {
-            None
+impl<T, const N: usize> Drop for SafeArrayInitialization<T, N> {
+    fn drop(&mut self) {
+        if let Some(mut array) = self.array.take() {
+            let array_ptr = array.as_mut_ptr() as *mut T;
+            for i in 0..self.count {
+                unsafe { reexport::drop_in_place(array_ptr.add(i)) };
+            }
         }
-    }}
+    }
+}
+
+/// Convert a slice to an array by cloning each element.
+/// Returns `None` if the slice does not have exactly `N` elements.
+/// If a clone panics, the elements cloned so far are dropped.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_to_array_clone<T: Clone, const N: usize>(slice: &[T]) -> Option<[T; N]> {
+    // Checked before the storage for the array is set up at all.
+    if !check::has_len(slice, N) {
+        report_length_mismatch::<T>(N, slice.len());
+        return None;
+    }
+    Some(SafeArrayInitialization::new().init_from_slice(slice))
 }
 
 /// Convert a slice to an array by cloning each element.
 /// `slice_to_array_clone!(slice, [element_type; array_length]) -> Option<[element_type; array_length]>`
+/// The element type may be left out, as in `slice_to_array_clone!(slice, array_length)`.
+/// With the `total_api` feature, `slice_to_array_clone!(slice, start..end, [element_type; array_length])`
+/// converts just that range, returning `None` instead of panicking if it is out of bounds.
 #[macro_export]
 macro_rules! slice_to_array_clone {
     ($slice:expr, [$t:ty ; $len:expr] ) => {{
-        struct SafeArrayInitialization {
-            array: Option<[$t; $len]>,
-            count: usize,
-        }
-        impl SafeArrayInitialization {
-            fn new() -> Self {
-                SafeArrayInitialization { array: Some(unsafe { $crate::reexport::uninitialized() }), count: 0 }
-            }
-            fn init_from_slice(mut self, slice: &[$t]) -> Option<[$t; $len]> {
-                {
-                    let array_mut: &mut [$t] = self.array.as_mut().unwrap().as_mut();
-                    if slice.len() != array_mut.len() {
-                        return None;
-                    }
-                    debug_assert_eq!(self.count, 0);
-                    for (val, ptr) in slice.iter().zip(array_mut.iter_mut()) {
-                        let val = $crate::reexport::clone(*val);
-                        unsafe { $crate::reexport::ptr_write(ptr, val) };
-                        self.count += 1;
-                    }
-                }
-                self.array.take()
-            }
-        }
-        impl Drop for SafeArrayInitialization {
-            fn drop(&mut self) {
-                if let Some(mut array) = self.array.take() {
-                    let count = self.count;
-                    {
-                        for ptr in array.as_mut()[..count].iter_mut() {
-                            unsafe { $crate::reexport::ptr_read(ptr) };
-                        }
-                    }
-                    $crate::reexport::forget(array);
-                }
-            }
-        }
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_clone::<$t, { $len }>(s)
+    }};
+    ($slice:expr, $start:tt .. $end:tt, [$t:ty ; $len:expr] ) => { $crate::__total_api! {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        match s.get($start..$end) { Some(s) => $crate::slice_to_array_clone::<$t, { $len }>(s), None => None }
+    }} };
+    ($slice:expr, $len:expr ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[_] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_clone::<_, { $len }>(s)
+    }}
+}
+
+/// Convert a slice to an array by cloning each element.
+/// Returns an error recording both lengths if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_to_array_clone_checked<T: Clone, const N: usize>(slice: &[T]) -> Result<[T; N], SliceLengthError> {
+    slice_to_array_clone(slice).ok_or(SliceLengthError { expected: N, actual: slice.len() })
+}
+
+/// Convert a slice to an array by cloning each element, reporting the lengths on mismatch.
+/// `slice_to_array_clone_checked!(slice, [element_type; array_length]) -> Result<[element_type; array_length], SliceLengthError>`
+/// The element type may be left out, as in `slice_to_array_clone_checked!(slice, array_length)`.
+#[macro_export]
+macro_rules! slice_to_array_clone_checked {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_clone_checked::<$t, { $len }>(s)
+    }};
+    ($slice:expr, $len:expr ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[_] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_clone_checked::<_, { $len }>(s)
+    }}
+}
+
+/// Convert a slice to an array without checking its length.
+///
+/// # Safety
+///
+/// The slice must have exactly `N` elements. This is checked with a
+/// `debug_assert!` in debug builds only.
+#[inline]
+pub unsafe fn slice_as_array_unchecked<T, const N: usize>(slice: &[T]) -> &[T; N] {
+    debug_assert_eq!(slice.len(), N);
+    let ptr = slice.as_ptr() as *const [T; N];
+    reexport::debug_assert_aligned(ptr);
+    &*ptr
+}
+
+/// Convert a mutable slice to a mutable array without checking its length.
+///
+/// # Safety
+///
+/// The slice must have exactly `N` elements. This is checked with a
+/// `debug_assert!` in debug builds only.
+#[inline]
+pub unsafe fn slice_as_array_unchecked_mut<T, const N: usize>(slice: &mut [T]) -> &mut [T; N] {
+    debug_assert_eq!(slice.len(), N);
+    let ptr = slice.as_mut_ptr() as *mut [T; N];
+    reexport::debug_assert_aligned(ptr);
+    &mut *ptr
+}
+
+/// Convert a slice to an array without checking its length. Must be used in an
+/// `unsafe` block, and the slice must have exactly `array_length` elements.
+/// `slice_as_array_unchecked!(slice, [element_type; array_length]) -> &[element_type; array_length]`
+#[macro_export]
+macro_rules! slice_as_array_unchecked {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_unchecked::<$t, { $len }>(s)
+    }}
+}
+
+/// Convert a mutable slice to a mutable array without checking its length. Must
+/// be used in an `unsafe` block, and the slice must have exactly `array_length` elements.
+/// `slice_as_array_unchecked_mut!(mutable_slice, [element_type; array_length]) -> &mut [element_type; array_length]`
+#[macro_export]
+macro_rules! slice_as_array_unchecked_mut {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_unchecked_mut::<$t, { $len }>(s)
+    }}
+}
+
+/// Convert a slice to an array by copying its elements in one go.
+/// Returns `None` if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_to_array_copy<T: Copy, const N: usize>(slice: &[T]) -> Option<[T; N]> {
+    // A single load of the whole array, which becomes one `memcpy`, with none
+    // of the per-element bookkeeping that `slice_to_array_clone` needs.
+    // `tests/codegen.rs` checks the optimized IR for exactly that.
+    slice_as_array(slice).copied()
+}
+
+/// Convert a slice of `Copy` elements to an array with a single memcpy.
+/// `slice_to_array_copy!(slice, [element_type; array_length]) -> Option<[element_type; array_length]>`
+/// The element type may be left out, as in `slice_to_array_copy!(slice, array_length)`.
+/// With the `total_api` feature, `slice_to_array_copy!(slice, start..end, [element_type; array_length])`
+/// converts just that range, returning `None` instead of panicking if it is out of bounds.
+#[macro_export]
+macro_rules! slice_to_array_copy {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_copy::<$t, { $len }>(s)
+    }};
+    ($slice:expr, $start:tt .. $end:tt, [$t:ty ; $len:expr] ) => { $crate::__total_api! {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        match s.get($start..$end) { Some(s) => $crate::slice_to_array_copy::<$t, { $len }>(s), None => None }
+    }} };
+    ($slice:expr, $len:expr ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[_] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_copy::<_, { $len }>(s)
+    }}
+}
+
+// Chooses how `slice_to_array` converts. It is private, so no other impls
+// can be added.
+trait ToArray: Clone {
+    fn to_array<const N: usize>(slice: &[Self]) -> Option<[Self; N]>;
+}
+
+#[cfg(not(feature="specialization"))]
+impl<T: Clone> ToArray for T {
+    #[inline]
+    #[track_caller]
+    fn to_array<const N: usize>(slice: &[T]) -> Option<[T; N]> {
+        slice_to_array_clone(slice)
+    }
+}
+
+#[cfg(feature="specialization")]
+impl<T: Clone> ToArray for T {
+    #[inline]
+    #[track_caller]
+    default fn to_array<const N: usize>(slice: &[T]) -> Option<[T; N]> {
+        slice_to_array_clone(slice)
+    }
+}
+
+#[cfg(feature="specialization")]
+impl<T: Copy> ToArray for T {
+    #[inline]
+    #[track_caller]
+    fn to_array<const N: usize>(slice: &[T]) -> Option<[T; N]> {
+        slice_to_array_copy(slice)
+    }
+}
+
+/// Convert a slice to an array of owned elements.
+/// Returns `None` if the slice does not have exactly `N` elements.
+/// The elements are cloned one by one, except with the nightly-only
+/// `specialization` feature, where `Copy` elements are copied all at once.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_to_array<T: Clone, const N: usize>(slice: &[T]) -> Option<[T; N]> {
+    T::to_array(slice)
+}
+
+/// Convert a slice to an array of owned elements, copying them if possible.
+/// `slice_to_array!(slice, [element_type; array_length]) -> Option<[element_type; array_length]>`
+/// The element type may be left out, as in `slice_to_array!(slice, array_length)`.
+#[macro_export]
+macro_rules! slice_to_array {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array::<$t, { $len }>(s)
+    }};
+    ($slice:expr, $len:expr ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[_] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array::<_, { $len }>(s)
+    }}
+}
+
+/// Convert a slice of `Copy` elements to an array. This is another name for
+/// `slice_to_array_copy!`, and takes the same arguments.
+/// `slice_as_array_copy!(slice, [element_type; array_length]) -> Option<[element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_array_copy {
+    ($($args:tt)*) => { $crate::slice_to_array_copy!($($args)*) }
+}
+
+/// Copy a slice into an existing array.
+/// Returns `false`, leaving `dst` untouched, if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+pub fn copy_slice_into_array<T: Copy, const N: usize>(dst: &mut [T; N], src: &[T]) -> bool {
+    if !check::has_len(src, N) {
+        report_length_mismatch::<T>(N, src.len());
+        return false;
+    }
+    unsafe { reexport::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), N) };
+    true
+}
+
+/// Clone a slice into an existing array, overwriting its elements in place.
+/// Returns `false`, leaving `dst` untouched, if the slice does not have exactly `N` elements.
+/// If a clone panics, `dst` is left with some elements overwritten and the
+/// rest as they were, each of them dropped exactly once.
+#[inline]
+#[track_caller]
+pub fn clone_slice_into_array<T: Clone, const N: usize>(dst: &mut [T; N], src: &[T]) -> bool {
+    if !check::has_len(src, N) {
+        report_length_mismatch::<T>(N, src.len());
+        return false;
+    }
+    dst.clone_from_slice(src);
+    true
+}
+
+/// Copy a slice into an existing array if the lengths match.
+/// `copy_slice_into_array!(&mut [element_type; array_length], slice) -> bool`
+#[macro_export]
+macro_rules! copy_slice_into_array {
+    ($dst:expr, $src:expr ) => {{
+        $crate::copy_slice_into_array($dst, $src)
+    }}
+}
+
+/// Clone a slice into an existing array if the lengths match.
+/// `clone_slice_into_array!(&mut [element_type; array_length], slice) -> bool`
+#[macro_export]
+macro_rules! clone_slice_into_array {
+    ($dst:expr, $src:expr ) => {{
+        $crate::clone_slice_into_array($dst, $src)
+    }}
+}
 
-        SafeArrayInitialization::new().init_from_slice($slice)
+/// Check whether a slice has exactly `N` elements, all equal to those of `array`.
+/// Elements are only compared once the lengths are known to match.
+#[inline]
+pub fn slice_eq_array<T: PartialEq, const N: usize>(slice: &[T], array: &[T; N]) -> bool {
+    // Compared directly, so that a mismatch is not reported as a failed conversion.
+    slice == array
+}
+
+/// Check whether a slice equals an array, in both length and content.
+/// `slice_eq_array!(slice, array) -> bool`
+#[macro_export]
+macro_rules! slice_eq_array {
+    ($slice:expr, $array:expr ) => {{
+        $crate::slice_eq_array($slice, &$array)
     }}
 }
 
 #[cfg(test)]
 mod test {
+    use super::{slice_as_array, slice_as_array_const, slice_as_array_mut, try_slice_as_array, SliceLengthError};
+    use super::{array_as_slice, array_as_mut_slice, array_as_slice_with_len};
+
     #[test]
     fn correct_length() {
         let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
@@ -183,6 +870,7 @@ mod test {
 
     #[test]
     #[should_panic]
+    #[allow(clippy::out_of_bounds_indexing)]
     fn overlong_length() {
         let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
         let xs_prefix: Option<&[u32; 8]> = slice_as_array!(&xs[0..8], [u32; 8]);
@@ -209,6 +897,47 @@ mod test {
         assert_eq!(xs_suffix[1][3], 33);
     }
 
+    #[test]
+    fn function_correct_length() {
+        let xs: [u8; 6] = [1, 2, 4, 8, 16, 32];
+        let xs_prefix = slice_as_array::<u8, 3>(&xs[..3]).expect("Length mismatch");
+        assert_eq!(xs_prefix, &[1, 2, 4]);
+        assert_eq!(slice_as_array::<u8, 4>(&xs[..3]), None);
+    }
+
+    #[test]
+    fn function_inferred_length() {
+        fn first_word(xs: &[u8]) -> Option<u32> {
+            let word: &[u8; 4] = slice_as_array(xs.get(..4)?)?;
+            Some(u32::from_le_bytes(*word))
+        }
+        assert_eq!(first_word(&[1, 0, 0, 0]), Some(1));
+        assert_eq!(first_word(&[1, 0]), None);
+    }
+
+    #[test]
+    fn try_correct_length() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        assert_eq!(try_slice_as_array!(&xs[1..4], [u32; 3]), Ok(&[2, 4, 8]));
+    }
+
+    #[test]
+    fn try_incorrect_length() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        assert_eq!(try_slice_as_array::<u32, 8>(&xs[1..4]), Err(SliceLengthError { expected: 8, actual: 3 }));
+    }
+
+    #[test]
+    fn function_mut() {
+        let mut xs: [u32; 12] = [0; 12];
+        {
+            let a: &mut [u32; 10] = slice_as_array_mut(&mut xs[..10]).unwrap();
+            a[9] = 7;
+        }
+        assert_eq!(xs[9], 7);
+        assert_eq!(slice_as_array_mut::<u32, 10>(&mut xs[..]), None);
+    }
+
     #[test]
     fn clone_correct() {
         let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
@@ -222,4 +951,508 @@ mod test {
         let xs_middle: Option<[u32; 3]> = slice_to_array_clone!(&xs[1..5], [u32; 3]);
         assert_eq!(xs_middle, None);
     }
+
+    #[test]
+    fn clone_strings() {
+        let xs: Vec<String> = (0..5).map(|i| i.to_string()).collect();
+        let xs_prefix: [String; 4] = slice_to_array_clone!(&xs[0..4], [String; 4]).unwrap();
+        assert_eq!(xs_prefix, ["0", "1", "2", "3"]);
+    }
+
+    #[test]
+    fn clone_references() {
+        static VALUES: [u32; 4] = [3, 1, 4, 1];
+        let refs: [&'static u32; 4] = [&VALUES[0], &VALUES[1], &VALUES[2], &VALUES[3]];
+        let cloned: [&u32; 4] = slice_to_array_clone!(&refs[..], [&'static u32; 4]).unwrap();
+        assert_eq!(cloned, [&3, &1, &4, &1]);
+    }
+
+    #[test]
+    fn copy_correct() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        let xs_middle: [u32; 3] = slice_to_array_copy!(&xs[1..4], [u32; 3]).expect("Length mismatch");
+        assert_eq!(xs_middle, [2, 4, 8]);
+    }
+
+    #[test]
+    fn copy_wrong_length() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        let xs_middle: Option<[u32; 3]> = slice_to_array_copy!(&xs[1..5], [u32; 3]);
+        assert_eq!(xs_middle, None);
+    }
+
+    #[test]
+    fn copy_into() {
+        let mut dst: [u32; 3] = [0; 3];
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        assert!(copy_slice_into_array!(&mut dst, &xs[1..4]));
+        assert_eq!(dst, [2, 4, 8]);
+        assert!(!copy_slice_into_array!(&mut dst, &xs[..]));
+        assert_eq!(dst, [2, 4, 8]);
+    }
+
+    #[test]
+    fn clone_into() {
+        let mut dst: [String; 2] = [String::new(), String::new()];
+        let xs: [String; 3] = ["a".to_string(), "b".to_string(), "c".to_string()];
+        assert!(clone_slice_into_array!(&mut dst, &xs[1..]));
+        assert_eq!(dst, ["b", "c"]);
+        assert!(!clone_slice_into_array!(&mut dst, &xs[..]));
+        assert_eq!(dst, ["b", "c"]);
+    }
+
+    #[test]
+    fn const_context() {
+        const TABLE: &[u8] = &[1, 2, 3, 4, 5];
+        const HEAD: &[u8; 4] = match slice_as_array_const(TABLE.split_at(4).0) {
+            Some(head) => head,
+            None => panic!("table too short"),
+        };
+        static MISSING: Option<&[u8; 4]> = slice_as_array_const(TABLE);
+        assert_eq!(HEAD, &[1, 2, 3, 4]);
+        assert_eq!(MISSING, None);
+    }
+
+    #[test]
+    fn array_to_slice() {
+        const ARRAY: [u8; 3] = [1, 2, 3];
+        const SLICE: &[u8] = array_as_slice(&ARRAY);
+        const WITH_LEN: (&[u8], usize) = array_as_slice_with_len(&ARRAY);
+        assert_eq!(SLICE, &[1, 2, 3]);
+        assert_eq!(WITH_LEN, (SLICE, 3));
+        let mut xs = [1u8, 2];
+        array_as_mut_slice(&mut xs)[0] = 5;
+        assert_eq!(xs, [5, 2]);
+    }
+
+    #[test]
+    fn unchecked() {
+        let mut xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        let xs_middle: &[u32; 3] = unsafe { slice_as_array_unchecked!(&xs[1..4], [u32; 3]) };
+        assert_eq!(xs_middle, &[2, 4, 8]);
+        let xs_prefix: &mut [u32; 2] = unsafe { slice_as_array_unchecked_mut!(&mut xs[..2], [u32; 2]) };
+        xs_prefix[0] = 0;
+        assert_eq!(xs[0], 0);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn unchecked_wrong_length() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        let _ = unsafe { slice_as_array_unchecked!(&xs[..], [u32; 3]) };
+    }
+
+    #[test]
+    fn inferred_element_type() {
+        let xs: [u8; 6] = [1, 2, 4, 8, 16, 32];
+        let a: &[u8; 4] = slice_as_array!(&xs[..4], 4).unwrap();
+        assert_eq!(a, &[1, 2, 4, 8]);
+        assert_eq!(try_slice_as_array!(&xs, 4).map(|a: &[u8; 4]| a[0]), Err(SliceLengthError { expected: 4, actual: 6 }));
+        assert_eq!(slice_to_array_clone!(&xs[2..], 4), Some([4, 8, 16, 32]));
+        assert_eq!(slice_to_array_copy!(&xs[2..], 4), Some([4, 8, 16, 32]));
+    }
+
+    #[test]
+    fn inferred_element_type_mut() {
+        let mut xs: Vec<u32> = vec![1, 2, 3];
+        {
+            let a: &mut [u32; 3] = slice_as_array_mut!(&mut xs, 3).unwrap();
+            a[0] = 10;
+        }
+        assert_eq!(xs, [10, 2, 3]);
+    }
+
+    #[test]
+    fn range() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        assert_eq!(slice_as_array!(&xs, 1..4, [u32; 3]), Some(&[2, 4, 8]));
+        assert_eq!(slice_as_array!(&xs, 1..4, [u32; 2]), None);
+        let start = 2;
+        let a: &[u32; 4] = slice_as_array!(&xs, start..(start + 4)).unwrap();
+        assert_eq!(a, &[4, 8, 16, 32]);
+    }
+
+    #[test]
+    fn range_out_of_bounds() {
+        // The same conversion as `overlong_length`, but without the panic.
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        assert_eq!(slice_as_array!(&xs, 0..8, [u32; 8]), None);
+        let (start, end) = (4, 2);
+        assert_eq!(slice_as_array!(&xs, start..end, [u32; 2]), None);
+    }
+
+    #[test]
+    #[allow(dead_code)]
+    fn hygiene() {
+        // The macros only name `$crate` items, so none of these can collide.
+        struct SafeArrayInitialization;
+        fn this_transmute() {}
+        mod reexport {}
+        let s: [u32; 4] = [1, 2, 3, 4];
+        let nested = slice_as_array!(slice_as_array!(&s, [u32; 4]).unwrap(), [u32; 4]);
+        assert_eq!(nested, Some(&s));
+        let mut t = s;
+        assert_eq!(slice_as_array_mut!(&mut t, [u32; 4]).map(|a| a[0]), Some(1));
+        assert_eq!(slice_to_array_clone!(&s, [u32; 4]), Some(s));
+    }
+
+    #[test]
+    fn eq_array() {
+        fn command(msg: &[u8]) -> &'static str {
+            match msg {
+                m if slice_eq_array!(m, [0xca, 0xfe]) => "hello",
+                m if slice_eq_array!(m, *b"bye") => "bye",
+                _ => "unknown",
+            }
+        }
+        assert_eq!(command(&[0xca, 0xfe]), "hello");
+        assert_eq!(command(b"bye"), "bye");
+        assert_eq!(command(&[0xca, 0xfe, 0]), "unknown");
+        assert_eq!(command(&[0xca]), "unknown");
+        assert_eq!(command(&[0xca, 0xff]), "unknown");
+    }
+
+    #[test]
+    fn zero_sized_elements() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Unit;
+        let xs = [(); 1000];
+        assert_eq!(slice_as_array!(&xs[..3], [(); 3]), Some(&[(); 3]));
+        assert_eq!(slice_as_array!(&xs[..3], [(); 4]), None);
+        assert_eq!(slice_as_array!(&xs[..0], [(); 0]), Some(&[]));
+        assert_eq!(slice_as_array!(&xs[..], [(); 1000]).map(|a| a.len()), Some(1000));
+        let mut units = [Unit; 5];
+        assert_eq!(slice_as_array_mut!(&mut units, [Unit; 5]).map(|a| a.len()), Some(5));
+        assert_eq!(slice_as_array_mut!(&mut units, [Unit; 0]), None);
+        assert_eq!(slice_to_array_clone!(&units[1..], [Unit; 4]), Some([Unit; 4]));
+        assert_eq!(slice_to_array_clone!(&units[..0], [Unit; 1]), None);
+        assert_eq!(slice_to_array_copy!(&units[..], [Unit; 5]), Some([Unit; 5]));
+    }
+
+    #[test]
+    fn clone_panic_drops_clones_once() {
+        use std::cell::{Cell, RefCell};
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        // Panics on the third call to `clone`, and records which clones are dropped.
+        struct Tracked<'a> {
+            id: usize,
+            clones: &'a Cell<usize>,
+            dropped: &'a RefCell<Vec<usize>>,
+        }
+
+        impl<'a> Clone for Tracked<'a> {
+            fn clone(&self) -> Self {
+                self.clones.set(self.clones.get() + 1);
+                if self.clones.get() == 3 {
+                    panic!("third clone");
+                }
+                Tracked { id: self.id + 100, clones: self.clones, dropped: self.dropped }
+            }
+        }
+
+        impl<'a> Drop for Tracked<'a> {
+            fn drop(&mut self) {
+                self.dropped.borrow_mut().push(self.id);
+            }
+        }
+
+        let clones = Cell::new(0);
+        let dropped = RefCell::new(Vec::new());
+        let xs: Vec<Tracked> = (0..4).map(|id| Tracked { id, clones: &clones, dropped: &dropped }).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| slice_to_array_clone!(&xs, [Tracked; 4])));
+        assert!(result.is_err());
+        assert_eq!(*dropped.borrow(), [100, 101]);
+        drop(xs);
+        assert_eq!(*dropped.borrow(), [100, 101, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn clone_checked() {
+        fn parse(xs: &[String]) -> Result<usize, SliceLengthError> {
+            let [a, b]: [String; 2] = slice_to_array_clone_checked!(xs, [String; 2])?;
+            Ok(a.len() + b.len())
+        }
+        let xs: Vec<String> = vec!["ab".into(), "c".into(), "d".into()];
+        assert_eq!(parse(&xs[..2]), Ok(3));
+        assert_eq!(parse(&xs), Err(SliceLengthError { expected: 2, actual: 3 }));
+        assert_eq!(slice_to_array_clone_checked!(&xs, [String; 3]).map(|[_, b, _]| b), Ok("c".to_string()));
+        let short: Result<[String; 2], _> = slice_to_array_clone_checked!(xs, 2);
+        assert_eq!(short, Err(SliceLengthError { expected: 2, actual: 3 }));
+    }
+
+    #[test]
+    fn nonempty() {
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        assert_eq!(slice_as_nonempty_array!(&xs[..1], [u32; 1]), Some(&[1]));
+        assert_eq!(slice_as_nonempty_array!(&xs[..0], [u32; 1]), None);
+    }
+
+    #[test]
+    fn as_ref_arguments() {
+        let xs: Vec<u32> = vec![1, 2, 3];
+        let boxed: Box<[u32]> = xs.clone().into_boxed_slice();
+        let array: [u32; 3] = [1, 2, 3];
+        assert_eq!(slice_as_array!(xs, [u32; 3]), Some(&[1, 2, 3]));
+        assert_eq!(slice_as_array!(&xs, [u32; 3]), Some(&[1, 2, 3]));
+        assert_eq!(slice_as_array!(boxed, 3), Some(&[1, 2, 3]));
+        assert_eq!(try_slice_as_array!(array, [u32; 3]), Ok(&[1, 2, 3]));
+        assert_eq!(slice_to_array_clone!(xs, [u32; 2]), None);
+        assert_eq!(slice_to_array_copy!(boxed, [u32; 3]), Some([1, 2, 3]));
+        assert_eq!(slice_to_array_clone_checked!(xs, 3), Ok([1, 2, 3]));
+        // The arguments were only borrowed.
+        assert_eq!((xs.len(), boxed.len()), (3, 3));
+    }
+
+    #[test]
+    fn as_mut_arguments() {
+        let mut xs: Vec<u32> = vec![1, 2, 3];
+        slice_as_array_mut!(&mut xs, [u32; 3]).unwrap()[0] = 10;
+        let mut array = [1u32, 2];
+        *slice_as_array_mut!(&mut array, 2).unwrap() = [7, 8];
+        {
+            let s: &mut [u32] = &mut xs;
+            slice_as_array_mut!(s, [u32; 3]).unwrap()[1] = 20;
+            // The slice was reborrowed, not moved.
+            slice_as_array_mut!(s, [u32; 3]).unwrap()[2] = 30;
+        }
+        assert_eq!(xs, [10, 20, 30]);
+        assert_eq!(array, [7, 8]);
+        xs.push(4);
+        assert_eq!(slice_as_array_mut!(&mut xs, [u32; 3]), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "is not aligned")]
+    fn misaligned_array_pointer() {
+        let words = [0u32; 2];
+        let ptr = (words.as_ptr() as *const u8).wrapping_add(1) as *const [u32; 1];
+        super::reexport::debug_assert_aligned(ptr);
+    }
+
+    #[test]
+    #[cfg(feature="total_api")]
+    fn total_api_ranges() {
+        let mut xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        assert_eq!(slice_to_array_copy!(&xs, 1..3, [u32; 2]), Some([2, 4]));
+        assert_eq!(slice_to_array_clone!(&xs, 4..8, [u32; 4]), None);
+        slice_as_array_mut!(&mut xs, 0..2, [u32; 2]).unwrap()[1] = 3;
+        assert_eq!(xs[1], 3);
+        let (start, end) = (5, 4);
+        assert_eq!(slice_as_array_mut!(&mut xs, start..end, [u32; 1]), None);
+    }
+
+    fn generic_prefix<const N: usize>(xs: &[u8]) -> Option<&[u8; N]> {
+        slice_as_array!(xs, [u8; N])
+    }
+
+    trait Record {
+        const LEN: usize;
+    }
+
+    struct Header;
+
+    impl Record for Header {
+        const LEN: usize = 4;
+    }
+
+    impl Header {
+        fn parse(xs: &[u8]) -> Option<&[u8; Self::LEN]> {
+            slice_as_array!(xs, [u8; Self::LEN])
+        }
+    }
+
+    #[test]
+    fn generic_length() {
+        let xs: [u8; 4] = [1, 2, 3, 4];
+        assert_eq!(generic_prefix::<4>(&xs), Some(&xs));
+        assert_eq!(generic_prefix::<3>(&xs), None);
+        let mut ys = xs;
+        fn generic_mut<const N: usize>(ys: &mut [u8]) -> Option<[u8; N]> {
+            slice_as_array_mut!(ys, [u8; N]).map(|array| { array.reverse(); *array })
+        }
+        assert_eq!(generic_mut::<4>(&mut ys), Some([4, 3, 2, 1]));
+        fn generic_copy<const N: usize>(xs: &[u8]) -> Option<[u8; N]> {
+            slice_to_array_copy!(xs, N)
+        }
+        assert_eq!(generic_copy::<2>(&xs[..2]), Some([1, 2]));
+    }
+
+    #[test]
+    fn associated_const_length() {
+        let xs: [u8; 4] = [1, 2, 3, 4];
+        assert_eq!(Header::parse(&xs), Some(&xs));
+        assert_eq!(slice_as_array!(&xs[..3], [u8; <Header as Record>::LEN]), None);
+        assert_eq!(try_slice_as_array!(&xs, [u8; Header::LEN]), Ok(&xs));
+    }
+
+    #[test]
+    fn as_array_copy() {
+        let xs: Vec<u16> = vec![1, 2, 3];
+        assert_eq!(slice_as_array_copy!(&xs[..], [u16; 3]), Some([1, 2, 3]));
+        assert_eq!(slice_as_array_copy!(&xs[..], [u16; 2]), None);
+        assert_eq!(slice_as_array_copy!(xs, 3), Some([1, 2, 3]));
+    }
+
+    #[test]
+    fn as_array_then() {
+        fn first_words(bytes: &[u8]) -> Option<[u32; 2]> {
+            slice_as_array_then!(&bytes[..8], [u8; 8], |head: &[u8; 8]| ::slice_to_int_array_le(head))
+        }
+        let bytes: [u8; 9] = [1, 0, 0, 0, 2, 0, 0, 0, 3];
+        assert_eq!(first_words(&bytes), Some([1, 2]));
+        let pair = slice_as_array_then!(&bytes[..2], [u8; 2], |pair| if pair[0] < pair[1] { Some(pair[0]) } else { None });
+        assert_eq!(pair, None);
+        let mut called = false;
+        assert_eq!(slice_as_array_then!(&bytes, [u8; 8], |_| { called = true; Some(()) }), None);
+        assert!(!called);
+    }
+
+    #[test]
+    fn as_array_then_lifetime() {
+        // The closure gets the array for the slice's whole lifetime, so it can
+        // return a borrow of it.
+        fn tail_pair(xs: &[u32]) -> Option<&[u32; 2]> {
+            super::slice_as_array_then(xs, |array: &[u32; 4]| slice_as_array!(&array[2..], [u32; 2]))
+        }
+        assert_eq!(tail_pair(&[1, 2, 3, 4]), Some(&[3, 4]));
+    }
+
+    #[test]
+    fn to_array() {
+        let words = [String::from("a"), String::from("b")];
+        assert_eq!(slice_to_array!(&words, [String; 2]), Some(words.clone()));
+        assert_eq!(slice_to_array!(&[1u8, 2, 3], 3), Some([1, 2, 3]));
+        assert_eq!(slice_to_array!(&[1u8, 2, 3], [u8; 2]), None);
+    }
+
+    #[test]
+    fn to_array_copy_skips_clone() {
+        use std::cell::Cell;
+        thread_local!(static CLONES: Cell<usize> = const { Cell::new(0) });
+        #[derive(Copy, Debug, PartialEq)]
+        struct Counted(u8);
+        #[allow(clippy::non_canonical_clone_impl)]
+        impl Clone for Counted {
+            fn clone(&self) -> Self { CLONES.with(|c| c.set(c.get() + 1)); *self }
+        }
+        assert_eq!(slice_to_array!(&[Counted(1), Counted(2)], 2), Some([Counted(1), Counted(2)]));
+        let expected = if cfg!(feature = "specialization") { 0 } else { 2 };
+        assert_eq!(CLONES.with(Cell::get), expected);
+    }
+
+    #[test]
+    fn clone_mismatch_is_inert() {
+        use std::cell::Cell;
+        thread_local!(static EVENTS: Cell<usize> = const { Cell::new(0) });
+        // Not zero-sized, so that the array being rejected has real storage.
+        struct Noisy([u8; 64]);
+        impl Clone for Noisy {
+            fn clone(&self) -> Self { EVENTS.with(|e| e.set(e.get() + 1)); Noisy(self.0) }
+        }
+        impl Drop for Noisy {
+            fn drop(&mut self) { EVENTS.with(|e| e.set(e.get() + 1)); }
+        }
+        let xs: Vec<Noisy> = (0..2000).map(|i| Noisy([i as u8; 64])).collect();
+        assert!(slice_to_array_clone!(&xs[..], [Noisy; 2001]).is_none());
+        assert!(slice_to_array_clone!(&xs[..1999], [Noisy; 2000]).is_none());
+        assert!(slice_to_array_clone!(&xs[..0], [Noisy; 4000]).is_none());
+        assert_eq!(EVENTS.with(Cell::get), 0);
+        // The counter does see the clones and drops of a conversion that succeeds.
+        let cloned = slice_to_array_clone!(&xs[7..10], [Noisy; 3]).unwrap();
+        assert_eq!(cloned[2].0, [9; 64]);
+        drop(cloned);
+        assert_eq!(EVENTS.with(Cell::get), 6);
+    }
+
+    #[test]
+    fn complex_element_types() {
+        use std::collections::HashMap;
+        let pairs: Vec<(u8, u8)> = vec![(1, 2), (3, 4), (5, 6)];
+        assert_eq!(slice_as_array!(&pairs, [(u8, u8); 3]), Some(&[(1, 2), (3, 4), (5, 6)]));
+        assert_eq!(slice_to_array_copy!(&pairs[1..], [(u8, u8); 2]), Some([(3, 4), (5, 6)]));
+        let names: [&'static str; 2] = ["a", "b"];
+        assert_eq!(try_slice_as_array!(&names[..], [&'static str; 2]), Ok(&names));
+        let mut nested: Vec<Option<Vec<u8>>> = vec![Some(vec![1])];
+        assert_eq!(slice_to_array_clone!(&nested, [Option<Vec<u8>>; 1]), Some([Some(vec![1])]));
+        slice_as_array_mut!(&mut nested, [Option<Vec<u8>>; 1]).unwrap()[0] = None;
+        assert_eq!(nested, [None]);
+        let maps: Vec<HashMap<u8, String>> = vec![HashMap::new(); 4];
+        assert!(slice_as_array!(&maps, [HashMap<u8, String>; 4]).is_some());
+        assert!(slice_to_array_clone!(&maps, [HashMap<u8, String>; 3]).is_none());
+    }
+
+    #[test]
+    fn reference_elements() {
+        // References and `Option<&T>` have niches, so no element may ever be
+        // left uninitialised on the way into an array.
+        let (a, b, c, d) = (1u32, 2, 3, 4);
+        let refs: Vec<&u32> = vec![&a, &b, &c, &d];
+        let borrowed: &[&u32; 4] = slice_as_array!(&refs, [&u32; 4]).unwrap();
+        assert_eq!(borrowed.map(|r| *r), [1, 2, 3, 4]);
+        assert!(std::ptr::eq(borrowed[2], &c));
+        let cloned: [&u32; 3] = slice_to_array_clone!(&refs[1..], [&u32; 3]).unwrap();
+        assert!(std::ptr::eq(cloned[0], &b));
+        assert_eq!(slice_to_array_copy!(&refs[..2], [&u32; 2]), Some([&a, &b]));
+        assert!(slice_to_array_clone!(&refs, [&u32; 5]).is_none());
+        assert!(slice_as_array!(&refs, [&u32; 3]).is_none());
+
+        let mut options: Vec<Option<&u32>> = vec![Some(&a), None, Some(&c)];
+        assert_eq!(slice_as_array!(&options, [Option<&u32>; 3]), Some(&[Some(&a), None, Some(&c)]));
+        assert_eq!(slice_to_array_clone!(&options, [Option<&u32>; 3]), Some([Some(&1), None, Some(&3)]));
+        assert_eq!(slice_to_array!(&options[1..], [Option<&u32>; 2]), Some([None, Some(&3)]));
+        slice_as_array_mut!(&mut options, [Option<&u32>; 3]).unwrap()[1] = Some(&d);
+        assert_eq!(options, [Some(&1), Some(&4), Some(&3)]);
+        assert!(slice_to_array_clone!(&options, [Option<&u32>; 2]).is_none());
+    }
+
+    #[test]
+    fn const_len() {
+        const BLOCK: usize = 4;
+        let xs = vec![1u8, 2, 3, 4];
+        assert_eq!(slice_as_array_const_len!(&xs, BLOCK), Some(&[1, 2, 3, 4]));
+        assert_eq!(slice_as_array_const_len!(xs, BLOCK, BLOCK % 2 == 0), Some(&[1, 2, 3, 4]));
+        assert_eq!(slice_as_array_const_len!(&xs[1..], 3), Some(&[2, 3, 4]));
+        assert_eq!(slice_as_array_const_len!(&xs, 2), None);
+
+        fn head<const N: usize>(xs: &[u8]) -> Option<&[u8; N]> {
+            slice_as_array_const_len!(&xs[..N], N, N <= 8)
+        }
+        assert_eq!(head::<2>(&xs), Some(&[1, 2]));
+    }
+
+    #[cfg(feature="log")]
+    #[test]
+    fn logs_mismatches() {
+        use std::sync::{Mutex, Once};
+        use ext::SliceAsArray;
+
+        static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        struct Capture;
+        impl log::Log for Capture {
+            fn enabled(&self, _: &log::Metadata) -> bool { true }
+            fn log(&self, record: &log::Record) { MESSAGES.lock().unwrap().push(record.args().to_string()) }
+            fn flush(&self) {}
+        }
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            log::set_logger(&Capture).unwrap();
+            log::set_max_level(log::LevelFilter::Debug);
+        });
+
+        let xs = [1u8, 2];
+        let (result, line) = (slice_as_array!(&xs, [u8; 3]), line!());
+        assert!(result.is_none());
+        let (cloned, clone_line) = (xs.to_array_clone::<4>(), line!());
+        assert!(cloned.is_none());
+        assert!(slice_as_array!(&xs, [u8; 2]).is_some());
+        let messages = MESSAGES.lock().unwrap();
+        let at = |line: u32, expected: &str| messages.iter().any(|message| {
+            message.starts_with(&format!("array conversion at src/lib.rs:{}:", line)) && message.ends_with(expected)
+        });
+        assert!(at(line, "failed: expected [u8; 3], found length 2"));
+        assert!(at(clone_line, "failed: expected [u8; 4], found length 2"));
+    }
 }
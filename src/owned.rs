@@ -0,0 +1,134 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions that move elements between owned arrays.
+
+#[cfg(feature="use_std")] use std::mem::{ManuallyDrop, MaybeUninit};
+#[cfg(not(feature="use_std"))] use core::mem::{ManuallyDrop, MaybeUninit};
+use reexport;
+
+struct AssertSum<const N: usize, const M: usize, const K: usize>;
+
+impl<const N: usize, const M: usize, const K: usize> AssertSum<N, M, K> {
+    // Evaluated, and so checked, when the function using it is monomorphized.
+    // `checked_add`, so that lengths whose sum overflows get this message too.
+    const HOLDS: () = assert!(matches!(N.checked_add(M), Some(sum) if sum == K), "array lengths do not add up");
+}
+
+/// Join two arrays into one, moving their elements without cloning.
+/// The length `K` of the result is usually inferred, and it is a compile
+/// error for it to be anything other than `N + M`.
+#[inline]
+pub fn concat_arrays<T, const N: usize, const M: usize, const K: usize>(a: [T; N], b: [T; M]) -> [T; K] {
+    let () = AssertSum::<N, M, K>::HOLDS;
+    // The elements are moved into `joined`, so the sources must not drop them.
+    let a = ManuallyDrop::new(a);
+    let b = ManuallyDrop::new(b);
+    let mut joined = MaybeUninit::<[T; K]>::uninit();
+    unsafe {
+        let joined_ptr = joined.as_mut_ptr() as *mut T;
+        reexport::copy_nonoverlapping(a.as_ptr(), joined_ptr, N);
+        reexport::copy_nonoverlapping(b.as_ptr(), joined_ptr.add(N), M);
+        joined.assume_init()
+    }
+}
+
+/// Join two arrays into one whose type spells out the length `N + M`.
+/// This needs a nightly toolchain and the `generic_const_exprs` feature.
+#[cfg(feature="generic_const_exprs")]
+#[inline]
+pub fn concat_arrays_exact<T, const N: usize, const M: usize>(a: [T; N], b: [T; M]) -> [T; N + M] where [(); N + M]: {
+    concat_arrays(a, b)
+}
+
+/// Join two arrays into one, moving their elements.
+/// `concat_arrays!([element_type; n], [element_type; m]) -> [element_type; n + m]`
+#[macro_export]
+macro_rules! concat_arrays {
+    ($a:expr, $b:expr ) => {{
+        $crate::concat_arrays($a, $b)
+    }}
+}
+
+/// Split an array into its first `A` and last `B` elements, moving them
+/// without cloning. It is a compile error for `A + B` to be anything other than `N`.
+#[inline]
+pub fn split_array_owned<T, const N: usize, const A: usize, const B: usize>(array: [T; N]) -> ([T; A], [T; B]) {
+    let () = AssertSum::<A, B, N>::HOLDS;
+    // The elements are moved into `head` and `tail`, so `array` must not drop them.
+    let array = ManuallyDrop::new(array);
+    let mut head = MaybeUninit::<[T; A]>::uninit();
+    let mut tail = MaybeUninit::<[T; B]>::uninit();
+    unsafe {
+        reexport::copy_nonoverlapping(array.as_ptr(), head.as_mut_ptr() as *mut T, A);
+        reexport::copy_nonoverlapping(array.as_ptr().add(A), tail.as_mut_ptr() as *mut T, B);
+        (head.assume_init(), tail.assume_init())
+    }
+}
+
+/// Split an array into two, moving its elements.
+/// `split_array_owned!([element_type; a + b], a, b) -> ([element_type; a], [element_type; b])`
+#[macro_export]
+macro_rules! split_array_owned {
+    ($array:expr, $a:expr, $b:expr ) => {{
+        let split: ([_; $a], [_; $b]) = $crate::split_array_owned($array);
+        split
+    }}
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn concat() {
+        let joined: [u8; 5] = concat_arrays!([1, 2], [3, 4, 5]);
+        assert_eq!(joined, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn concat_empty() {
+        let joined: [u8; 2] = concat_arrays!([], [1, 2]);
+        assert_eq!(joined, [1, 2]);
+    }
+
+    #[test]
+    fn concat_moves() {
+        let a = ["a".to_string(), "b".to_string()];
+        let b = ["c".to_string()];
+        let joined: [String; 3] = concat_arrays!(a, b);
+        assert_eq!(joined, ["a", "b", "c"]);
+    }
+
+    #[test]
+    #[cfg(feature="generic_const_exprs")]
+    fn concat_exact() {
+        assert_eq!(super::concat_arrays_exact([1, 2], [3]), [1, 2, 3]);
+    }
+
+    #[test]
+    fn split() {
+        let (head, tail) = split_array_owned!([1, 2, 3, 4, 5], 2, 3);
+        assert_eq!(head, [1, 2]);
+        assert_eq!(tail, [3, 4, 5]);
+    }
+
+    #[test]
+    fn split_moves() {
+        let strings = ["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string(), "e".to_string()];
+        let (head, tail): ([String; 2], [String; 3]) = split_array_owned!(strings, 2, 3);
+        assert_eq!(head, ["a", "b"]);
+        assert_eq!(tail, ["c", "d", "e"]);
+    }
+
+    #[test]
+    fn split_round_trip() {
+        let (head, tail) = split_array_owned!([1, 2, 3], 0, 3);
+        let joined: [u8; 3] = concat_arrays!(head, tail);
+        assert_eq!(joined, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions that fill the array past the end of a short slice.
+
+use {report_mismatch, Mismatch, SafeArrayInitialization};
+
+/// Convert a slice to an array, cloning its first `N` elements and filling
+/// any positions past the end of the slice with `T::default()`.
+/// Elements beyond the first `N` are ignored.
+pub fn slice_to_array_default<T: Default + Clone, const N: usize>(slice: &[T]) -> [T; N] {
+    slice_to_array_with(slice, |_| T::default())
+}
+
+/// Convert a slice to an array, filling any positions past the end of the
+/// slice with `T::default()`.
+/// Returns `None` if the slice has more than `N` elements.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_to_array_default_fallible<T: Default + Clone, const N: usize>(slice: &[T]) -> Option<[T; N]> {
+    if slice.len() > N {
+        report_mismatch::<T>(N, Mismatch::TooLong(slice.len()));
+        return None;
+    }
+    Some(slice_to_array_default(slice))
+}
+
+/// Convert a slice to an array, padding with `Default::default()` and truncating if too long.
+/// `slice_to_array_default!(slice, [element_type; array_length]) -> [element_type; array_length]`
+#[macro_export]
+macro_rules! slice_to_array_default {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_default::<$t, { $len }>(s)
+    }}
+}
+
+/// Convert a slice to an array, padding with `Default::default()`.
+/// `slice_to_array_default_fallible!(slice, [element_type; array_length]) -> Option<[element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_to_array_default_fallible {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_default_fallible::<$t, { $len }>(s)
+    }}
+}
+
+/// Convert a slice to an array, cloning its first `N` elements and filling
+/// any positions past the end of the slice with clones of `fill`.
+/// Elements beyond the first `N` are ignored, and `fill` is dropped if no
+/// padding was needed.
+pub fn slice_to_array_or<T: Clone, const N: usize>(slice: &[T], fill: T) -> [T; N] {
+    let mut init = SafeArrayInitialization::new();
+    for val in slice.iter().take(N) {
+        init.push(val.clone());
+    }
+    if init.count < N {
+        while init.count < N - 1 {
+            init.push(fill.clone());
+        }
+        init.push(fill);
+    }
+    init.finish()
+}
+
+/// Convert a slice to an array, padding with clones of a fill value and truncating if too long.
+/// `slice_to_array_or!(slice, fill, [element_type; array_length]) -> [element_type; array_length]`
+#[macro_export]
+macro_rules! slice_to_array_or {
+    ($slice:expr, $fill:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_or::<$t, { $len }>(s, $fill)
+    }}
+}
+
+/// Convert a slice to an array, cloning its first `N` elements and filling
+/// any position `i` past the end of the slice with `f(i)`.
+/// Elements beyond the first `N` are ignored, and `f` is only called for
+/// the padding positions.
+pub fn slice_to_array_with<T: Clone, F: FnMut(usize) -> T, const N: usize>(slice: &[T], mut f: F) -> [T; N] {
+    let mut init = SafeArrayInitialization::new();
+    for val in slice.iter().take(N) {
+        init.push(val.clone());
+    }
+    while init.count < N {
+        let val = f(init.count);
+        init.push(val);
+    }
+    init.finish()
+}
+
+/// Convert a slice to an array, padding with values computed from their index and truncating if too long.
+/// `slice_to_array_with!(slice, [element_type; array_length], |index| element) -> [element_type; array_length]`
+#[macro_export]
+macro_rules! slice_to_array_with {
+    ($slice:expr, [$t:ty ; $len:expr], $f:expr ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_to_array_with::<$t, _, { $len }>(s, $f)
+    }}
+}
+
+/// Build an array like `core::array::from_fn`, but taking the elements at
+/// indices within the slice from clones of it. This is another name for
+/// `slice_to_array_with`: `f(i)` is only called for `i >= slice.len()`, and
+/// the elements built so far are dropped if a clone or `f` panics.
+#[inline]
+pub fn array_from_slice_or_fn<T: Clone, F: FnMut(usize) -> T, const N: usize>(slice: &[T], f: F) -> [T; N] {
+    slice_to_array_with(slice, f)
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use super::{slice_to_array_or, slice_to_array_with, array_from_slice_or_fn};
+
+    #[test]
+    fn default_as_ref() {
+        let xs: Vec<u32> = vec![1, 2];
+        assert_eq!(slice_to_array_default!(xs, [u32; 3]), [1, 2, 0]);
+        assert_eq!(slice_to_array_default_fallible!([1u32, 2, 3], [u32; 3]), Some([1, 2, 3]));
+    }
+
+    #[test]
+    fn default_short() {
+        let xs: [u32; 2] = [1, 2];
+        assert_eq!(slice_to_array_default!(&xs[..], [u32; 4]), [1, 2, 0, 0]);
+    }
+
+    #[test]
+    fn default_exact() {
+        let xs: [String; 2] = ["a".to_string(), "b".to_string()];
+        assert_eq!(slice_to_array_default!(&xs[..], [String; 2]), ["a", "b"]);
+    }
+
+    #[test]
+    fn default_truncates() {
+        let xs: [u32; 5] = [1, 2, 3, 4, 5];
+        assert_eq!(slice_to_array_default!(&xs[..], [u32; 3]), [1, 2, 3]);
+    }
+
+    #[test]
+    fn default_fallible() {
+        let xs: [u32; 5] = [1, 2, 3, 4, 5];
+        assert_eq!(slice_to_array_default_fallible!(&xs[..2], [u32; 3]), Some([1, 2, 0]));
+        assert_eq!(slice_to_array_default_fallible!(&xs[..], [u32; 5]), Some(xs));
+        assert_eq!(slice_to_array_default_fallible!(&xs[..], [u32; 3]), None);
+    }
+
+    #[test]
+    fn or_short() {
+        let xs: [u8; 2] = [1, 2];
+        assert_eq!(slice_to_array_or!(&xs[..], 0xff, [u8; 4]), [1, 2, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn or_truncates() {
+        let xs: [u8; 5] = [1, 2, 3, 4, 5];
+        assert_eq!(slice_to_array_or!(&xs[..], 0xff, [u8; 3]), [1, 2, 3]);
+    }
+
+    // Counts drops, and panics when cloned after `clones_left` reaches zero.
+    struct Tracked<'a> {
+        clones_left: &'a Cell<usize>,
+        drops: &'a Cell<usize>,
+    }
+
+    impl<'a> Clone for Tracked<'a> {
+        fn clone(&self) -> Self {
+            if self.clones_left.get() == 0 {
+                panic!("out of clones");
+            }
+            self.clones_left.set(self.clones_left.get() - 1);
+            Tracked { clones_left: self.clones_left, drops: self.drops }
+        }
+    }
+
+    impl<'a> Drop for Tracked<'a> {
+        fn drop(&mut self) {
+            self.drops.set(self.drops.get() + 1);
+        }
+    }
+
+    #[test]
+    fn or_fill_unused() {
+        let (clones_left, drops) = (Cell::new(10), Cell::new(0));
+        let xs = [Tracked { clones_left: &clones_left, drops: &drops }, Tracked { clones_left: &clones_left, drops: &drops }];
+        let fill = Tracked { clones_left: &clones_left, drops: &drops };
+        let array: [Tracked; 2] = slice_to_array_or(&xs[..], fill);
+        assert_eq!((clones_left.get(), drops.get()), (8, 1));
+        drop(array);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn or_fill_moved() {
+        let (clones_left, drops) = (Cell::new(10), Cell::new(0));
+        let xs = [Tracked { clones_left: &clones_left, drops: &drops }];
+        let fill = Tracked { clones_left: &clones_left, drops: &drops };
+        let array: [Tracked; 3] = slice_to_array_or(&xs[..], fill);
+        assert_eq!((clones_left.get(), drops.get()), (8, 0));
+        drop(array);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn or_clone_panic() {
+        let (clones_left, drops) = (Cell::new(3), Cell::new(0));
+        let xs = [Tracked { clones_left: &clones_left, drops: &drops }, Tracked { clones_left: &clones_left, drops: &drops }];
+        let fill = Tracked { clones_left: &clones_left, drops: &drops };
+        let result = catch_unwind(AssertUnwindSafe(|| { let _: [Tracked; 5] = slice_to_array_or(&xs[..], fill); }));
+        assert!(result.is_err());
+        // Two cloned elements, one cloned fill, and the fill itself.
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn with_index() {
+        let xs: [usize; 2] = [7, 7];
+        let mut calls = Vec::new();
+        let array = slice_to_array_with!(&xs[..], [usize; 5], |i| { calls.push(i); i * 10 });
+        assert_eq!(array, [7, 7, 20, 30, 40]);
+        assert_eq!(calls, [2, 3, 4]);
+    }
+
+    #[test]
+    fn with_not_called() {
+        let xs: [usize; 3] = [1, 2, 3];
+        let array = slice_to_array_with!(&xs[..], [usize; 3], |_| -> usize { panic!("no padding needed") });
+        assert_eq!(array, [1, 2, 3]);
+    }
+
+    #[test]
+    fn with_closure_panic() {
+        let (clones_left, drops) = (Cell::new(10), Cell::new(0));
+        let xs = [Tracked { clones_left: &clones_left, drops: &drops }];
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _: [Tracked; 4] = slice_to_array_with(&xs[..], |i| {
+                if i == 3 {
+                    panic!("no element {}", i);
+                }
+                Tracked { clones_left: &clones_left, drops: &drops }
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn from_slice_or_fn() {
+        let names = [String::from("a")];
+        let array: [String; 3] = array_from_slice_or_fn(&names, |i| format!("#{}", i));
+        assert_eq!(array, ["a", "#1", "#2"]);
+    }
+
+    #[test]
+    fn from_slice_or_fn_clone_panic() {
+        let (clones_left, drops) = (Cell::new(1), Cell::new(0));
+        let xs = [Tracked { clones_left: &clones_left, drops: &drops }, Tracked { clones_left: &clones_left, drops: &drops }];
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _: [Tracked; 3] = array_from_slice_or_fn(&xs[..], |_| -> Tracked { panic!("not reached") });
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 1);
+    }
+}
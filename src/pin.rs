@@ -0,0 +1,74 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions of pinned slices.
+//!
+//! Only the pinning of the outer reference matters: reinterpreting a slice as
+//! an array neither moves nor copies its elements, so an array reference to
+//! pinned elements may itself be pinned.
+
+#[cfg(feature="use_std")] use std::pin::Pin;
+#[cfg(not(feature="use_std"))] use core::pin::Pin;
+use {slice_as_array, slice_as_array_mut};
+
+/// Convert a pinned slice to a pinned array.
+/// Returns `None` if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn pin_slice_as_array<T, const N: usize>(slice: Pin<&[T]>) -> Option<Pin<&[T; N]>> {
+    let array = slice_as_array(slice.get_ref())?;
+    // The array is the same elements at the same address, which stay pinned.
+    Some(unsafe { Pin::new_unchecked(array) })
+}
+
+/// Convert a pinned mutable slice to a pinned mutable array.
+/// Returns `None` if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn pin_slice_as_array_mut<T, const N: usize>(slice: Pin<&mut [T]>) -> Option<Pin<&mut [T; N]>> {
+    // Nothing is moved out of the unpinned reference; it is only cast.
+    let array = slice_as_array_mut(unsafe { slice.get_unchecked_mut() })?;
+    Some(unsafe { Pin::new_unchecked(array) })
+}
+
+#[cfg(test)]
+mod test {
+    use std::marker::PhantomPinned;
+    use std::pin::Pin;
+    use super::{pin_slice_as_array, pin_slice_as_array_mut};
+
+    struct Pinned {
+        value: u32,
+        _pin: PhantomPinned,
+    }
+
+    #[test]
+    fn pinned() {
+        let xs = [1u32, 2, 3];
+        let pinned = Pin::new(&xs[..]);
+        assert_eq!(pin_slice_as_array(pinned).map(Pin::get_ref), Some(&[1, 2, 3]));
+        assert!(pin_slice_as_array::<_, 2>(pinned).is_none());
+    }
+
+    #[test]
+    fn pinned_mut() {
+        let mut buffer = Box::pin([Pinned { value: 1, _pin: PhantomPinned }, Pinned { value: 2, _pin: PhantomPinned }]);
+        let address = buffer.as_ptr() as usize;
+        let slice: Pin<&mut [Pinned]> = unsafe { buffer.as_mut().map_unchecked_mut(|a| &mut a[..]) };
+        let array = pin_slice_as_array_mut::<_, 2>(slice).unwrap();
+        assert_eq!(array.as_ptr() as usize, address);
+        let array = unsafe { array.get_unchecked_mut() };
+        array[1].value += 40;
+        assert_eq!((buffer[0].value, buffer[1].value), (1, 42));
+        let slice: Pin<&mut [Pinned]> = unsafe { buffer.as_mut().map_unchecked_mut(|a| &mut a[..]) };
+        assert!(pin_slice_as_array_mut::<_, 3>(slice).is_none());
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Everything needed to use the crate, for `use slice_as_array::prelude::*;`.
+//!
+//! This brings in the `SliceAsArray` extension trait, the error types, and
+//! every conversion function. Each macro is imported along with the function
+//! of the same name, so `#[macro_use]` is not needed either; the macros
+//! without a function of their own are listed separately. Items behind the
+//! `alloc` feature or an interop feature are only included when it is enabled.
+
+pub use {SliceAsArray, SliceLengthError, ConversionError, TryArrayError, MapArrayError, Pod, Integer, SameLayout};
+pub use {ArrayTuple, TupleArray};
+pub use {ArrayChunks, ArrayChunksMut, ArrayWindows, ExactArrayChunks, RArrayChunks};
+pub use {FixedArrayRef, FixedArrayMut, ArrayBuilder};
+
+pub use {slice_as_array, slice_as_array_const, try_slice_as_array, slice_as_nonempty_array, slice_as_array_mut};
+pub use {slice_to_array, slice_to_array_clone, slice_to_array_clone_checked, slice_to_array_copy};
+pub use {slice_as_array_unchecked, slice_as_array_unchecked_mut};
+pub use {copy_slice_into_array, clone_slice_into_array, slice_eq_array, slice_as_array_then};
+pub use {array_as_slice, array_as_mut_slice, array_as_slice_with_len};
+pub use {slice_as_array_expect, slice_as_array_mut_expect, slice_to_array_clone_expect};
+pub use {try_from_slice, try_from_slice_mut, try_from_slice_cloned};
+pub use {array_from_iter, array_from_iter_exact, try_array_from_iter, slice_map_to_array};
+pub use {slice_as_array_prefix, slice_as_array_prefix_mut, slice_as_array_suffix, slice_as_array_suffix_mut};
+pub use {slice_split_array, slice_split_array_mut, slice_clone_split, slice_rsplit_array, slice_rsplit_array_mut};
+pub use {slice_as_array_at, slice_as_array_at_mut, slice_as_array_range, slice_two_arrays_mut};
+pub use {slice_as_array_at_saturating, slice_as_array_at_saturating_mut};
+pub use {slice_split_mid_array, slice_split_mid_array_mut};
+pub use {zip_slices_as_arrays, zip_slices_as_arrays_mut, strip_prefix_as_array};
+pub use {slice_to_array_default, slice_to_array_default_fallible, slice_to_array_or, slice_to_array_with};
+pub use array_from_slice_or_fn;
+pub use {pin_slice_as_array, pin_slice_as_array_mut};
+pub use {uninit_slice_as_array_mut, assume_init_array, assume_init_array_mut};
+pub use {transmute_array_ref, transmute_array_mut};
+pub use {array_chunks_ref, array_chunks_mut, exact_array_chunks, array_windows, rarray_chunks};
+pub use {slice_as_2d_array, slice_as_2d_array_mut, slice_as_2d_array_rows_mut, row_as_array, row_as_array_mut};
+pub use {slice_as_array_slice, slice_as_array_slice_mut};
+pub use {array_flatten, array_flatten_mut, array_reshape, array_reshape_mut, array_reshape_owned};
+pub use {concat_arrays, split_array_owned, array_as_tuple, tuple_as_array};
+pub use {bytes_as_array, slice_to_int_array_le, slice_to_int_array_be, str_as_byte_array, byte_array_as_str};
+#[cfg(feature="generic_const_exprs")]
+pub use {concat_arrays_exact, array_flatten_to_array, array_flatten_to_array_mut};
+
+#[cfg(feature="alloc")]
+pub use {vec_into_array, box_slice_into_array, rc_slice_into_array, arc_slice_into_array, array_into_vec, array_as_vec, cow_slice_as_array, take_vec_into_array};
+#[cfg(feature="alloc")]
+pub use vec_split_off_array;
+#[cfg(feature="use_std")]
+pub use read_exact_array;
+#[cfg(feature="arrayvec")]
+pub use {arrayvec_into_array, arrayvec_as_array};
+#[cfg(feature="generic-array")]
+pub use {genericarray_as_array, genericarray_into_array};
+#[cfg(feature="heapless")]
+pub use heapless_into_array;
+#[cfg(feature="smallvec")]
+pub use smallvec_into_array;
+#[cfg(feature="tinyvec")]
+pub use tinyvec_arrayvec_into_array;
+#[cfg(all(feature="tinyvec", feature="alloc"))]
+pub use tinyvec_into_array;
+#[cfg(feature="zerocopy")]
+pub use from_bytes_array;
+
+pub use {checked_slice_as_array, collect_array, windows_as_arrays, slice_as_array_copy};
+pub use slice_as_array_const_len;
+pub use {first_array, first_array_mut, last_array, last_array_mut};
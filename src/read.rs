@@ -0,0 +1,59 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reading arrays from byte streams.
+
+use std::io::{self, Read};
+
+/// Read exactly `N` bytes from `reader` into an array, e.g. a fixed-size header.
+/// Returns an error of kind `UnexpectedEof` if the stream ends first, or any
+/// other error from `reader`, as `Read::read_exact` does.
+#[inline]
+pub fn read_exact_array<R: Read + ?Sized, const N: usize>(reader: &mut R) -> io::Result<[u8; N]> {
+    let mut array = [0u8; N];
+    reader.read_exact(&mut array)?;
+    Ok(array)
+}
+
+/// Read exactly the given number of bytes from a reader into an array.
+/// `read_exact_array!(&mut reader, array_length) -> io::Result<[u8; array_length]>`
+#[macro_export]
+macro_rules! read_exact_array {
+    ($reader:expr, $len:expr ) => {{
+        $crate::read_exact_array::<_, { $len }>($reader)
+    }}
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{self, Read};
+    use super::read_exact_array;
+
+    #[test]
+    fn header_then_body() {
+        let mut stream: &[u8] = b"HDR\x02body";
+        let magic: [u8; 3] = read_exact_array(&mut stream).unwrap();
+        assert_eq!(&magic, b"HDR");
+        assert_eq!(read_exact_array!(&mut stream, 1).unwrap(), [2]);
+        assert_eq!(stream, b"body");
+    }
+
+    #[test]
+    fn short_read() {
+        let mut stream: &[u8] = b"abc";
+        let error = read_exact_array!(&mut stream, 4).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn dyn_reader() {
+        let mut reader: Box<dyn Read> = Box::new(io::repeat(7));
+        assert_eq!(read_exact_array!(&mut *reader, 2).unwrap(), [7, 7]);
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reporting of failed conversions behind the `log` and `tracing` features.
+//! Without either feature everything here is empty, and compiles to nothing.
+
+#[cfg(all(any(feature="log", feature="tracing"), feature="use_std"))] use std::{any, fmt, panic::Location};
+#[cfg(all(any(feature="log", feature="tracing"), not(feature="use_std")))] use core::{any, fmt, panic::Location};
+#[cfg(any(feature="log", feature="tracing"))]
+use {ConversionError, SliceLengthError};
+
+/// Why the input of a conversion to `[T; N]` did not fit.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(not(any(feature="log", feature="tracing")), allow(dead_code))]
+pub enum Mismatch {
+    /// It had this many elements rather than exactly `N`.
+    Length(usize),
+    /// It had this many elements, fewer than the `N` it needed at least.
+    TooShort(usize),
+    /// It had this many elements, more than the `N` it allowed at most.
+    TooLong(usize),
+    /// It had more than `N` elements, without counting how many more.
+    MoreThan,
+    /// It had this many elements, which is not a multiple of `N`.
+    NotMultiple(usize),
+    /// The `N` elements at `start..end` do not lie within a slice of `len`.
+    OutOfBounds { start: usize, end: usize, len: usize },
+    /// Row `row` of `row_len` elements would start past `usize::MAX`.
+    RowOverflow { row: usize, row_len: usize },
+    /// Its address was `offset` bytes past a multiple of `align`.
+    Misaligned { align: usize, offset: usize },
+    /// The `N` elements at `offset` overlap the `other_len` at `other_offset`.
+    Overlapping { offset: usize, other_offset: usize, other_len: usize },
+    /// It did not start with the given prefix of this many elements.
+    WrongPrefix(usize),
+}
+
+// The text after "failed: " in a report, naming the array type.
+#[cfg(any(feature="log", feature="tracing"))]
+struct Report {
+    element_type: &'static str,
+    len: usize,
+    mismatch: Mismatch,
+}
+
+#[cfg(any(feature="log", feature="tracing"))]
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (t, n) = (self.element_type, self.len);
+        match self.mismatch {
+            Mismatch::Length(actual) => {
+                let error = SliceLengthError { expected: n, actual };
+                write!(f, "{}", ConversionError { length: error, element_type: t })
+            }
+            Mismatch::TooShort(actual) => write!(f, "expected a length of at least {} for [{}; {}], found length {}", n, t, n, actual),
+            Mismatch::TooLong(actual) => write!(f, "expected a length of at most {} for [{}; {}], found length {}", n, t, n, actual),
+            Mismatch::MoreThan => write!(f, "expected [{}; {}], found a length of more than {}", t, n, n),
+            Mismatch::NotMultiple(actual) => write!(f, "expected a length that is a multiple of {} for [{}; {}], found length {}", n, t, n, actual),
+            Mismatch::OutOfBounds { start, end, len } => write!(f, "expected [{}; {}] at {}..{}, found a slice of length {}", t, n, start, end, len),
+            Mismatch::RowOverflow { row, row_len } => write!(f, "expected [{}; {}] as row {}, found its offset {} * {} overflowing usize", t, n, row, row, row_len),
+            Mismatch::Misaligned { align, offset } => write!(f, "expected [{}; {}] aligned to {} bytes, found an address misaligned by {}", t, n, align, offset),
+            Mismatch::Overlapping { offset, other_offset, other_len } => {
+                write!(f, "expected [{}; {}] at offset {} apart from [{}; {}] at offset {}, found them overlapping", t, n, offset, t, other_len, other_offset)
+            }
+            Mismatch::WrongPrefix(prefix_len) => write!(f, "expected [{}; {}] after a prefix of length {}, found a slice not starting with it", t, n, prefix_len),
+        }
+    }
+}
+
+/// Report that a conversion to `[T; len]` failed, and why, along with where
+/// it was called from. Kept out of line so the conversions' own code stays
+/// small when a feature is on.
+#[cfg_attr(any(feature="log", feature="tracing"), cold, inline(never))]
+#[cfg_attr(not(any(feature="log", feature="tracing")), inline)]
+#[track_caller]
+#[cfg_attr(not(any(feature="log", feature="tracing")), allow(clippy::extra_unused_type_parameters))]
+pub fn report_mismatch<T>(len: usize, mismatch: Mismatch) {
+    #[cfg(any(feature="log", feature="tracing"))]
+    let (location, error) = (Location::caller(), Report { element_type: any::type_name::<T>(), len, mismatch });
+    #[cfg(feature="log")]
+    log::debug!("array conversion at {} failed: {}", location, error);
+    #[cfg(feature="tracing")]
+    tracing::debug!(%location, %error, "array conversion failed");
+    #[cfg(not(any(feature="log", feature="tracing")))]
+    let _ = (len, mismatch);
+}
+
+/// Report that a conversion needing exactly `expected` elements found `actual`.
+#[inline]
+#[track_caller]
+pub fn report_length_mismatch<T>(expected: usize, actual: usize) {
+    report_mismatch::<T>(expected, Mismatch::Length(actual));
+}
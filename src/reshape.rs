@@ -0,0 +1,461 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions between flat and nested arrays.
+
+#[cfg(feature="use_std")] use std::{mem::ManuallyDrop, slice};
+#[cfg(not(feature="use_std"))] use core::{mem::ManuallyDrop, slice};
+use {check, reexport, report_length_mismatch, report_mismatch, slice_as_array_at, slice_as_array_at_mut, Mismatch};
+
+/// Reinterpret a flat slice of `N * M` elements as `N` rows of `M` elements.
+/// Returns `None` if the slice does not have exactly `N * M` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_2d_array<T, const M: usize, const N: usize>(slice: &[T]) -> Option<&[[T; M]; N]> {
+    if Some(slice.len()) == N.checked_mul(M) {
+        // Nested arrays are laid out contiguously, row after row.
+        let ptr = slice.as_ptr() as *const [[T; M]; N];
+        reexport::debug_assert_aligned(ptr);
+        Some( unsafe { &*ptr } )
+    } else {
+        report_length_mismatch::<T>(N.saturating_mul(M), slice.len());
+        None
+    }
+}
+
+/// Reinterpret a flat mutable slice of `N * M` elements as `N` rows of `M` elements.
+/// Returns `None` if the slice does not have exactly `N * M` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_2d_array_mut<T, const M: usize, const N: usize>(slice: &mut [T]) -> Option<&mut [[T; M]; N]> {
+    if Some(slice.len()) == N.checked_mul(M) {
+        let ptr = slice.as_mut_ptr() as *mut [[T; M]; N];
+        reexport::debug_assert_aligned(ptr);
+        Some( unsafe { &mut *ptr } )
+    } else {
+        report_length_mismatch::<T>(N.saturating_mul(M), slice.len());
+        None
+    }
+}
+
+/// Iterate over the `N` rows of a flat mutable slice of `N * M` elements, as
+/// disjoint `&mut [T; M]` that can be handed out separately, e.g. to threads.
+/// Returns `None` if the slice does not have exactly `N * M` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_2d_array_rows_mut<'a, T, const M: usize, const N: usize>(slice: &'a mut [T]) -> Option<slice::IterMut<'a, [T; M]>> {
+    slice_as_2d_array_mut::<T, M, N>(slice).map(|rows| rows.iter_mut())
+}
+
+/// Reinterpret a flat slice as a two-dimensional array in row-major order.
+/// `slice_as_2d_array!(slice, [[element_type; row_length]; row_count]) -> Option<&[[element_type; row_length]; row_count]>`
+#[macro_export]
+macro_rules! slice_as_2d_array {
+    ($slice:expr, [[$t:ty ; $m:expr] ; $n:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_2d_array::<$t, { $m }, { $n }>(s)
+    }}
+}
+
+/// Reinterpret a flat mutable slice as a mutable two-dimensional array in row-major order.
+/// `slice_as_2d_array_mut!(mutable_slice, [[element_type; row_length]; row_count]) -> Option<&mut [[element_type; row_length]; row_count]>`
+#[macro_export]
+macro_rules! slice_as_2d_array_mut {
+    ($slice:expr, [[$t:ty ; $m:expr] ; $n:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_2d_array_mut::<$t, { $m }, { $n }>(s)
+    }}
+}
+
+/// Iterate over the rows of a flat mutable slice as disjoint mutable arrays.
+/// `slice_as_2d_array_rows_mut!(mutable_slice, [[element_type; row_length]; row_count]) -> Option<impl Iterator<Item = &mut [element_type; row_length]>>`
+#[macro_export]
+macro_rules! slice_as_2d_array_rows_mut {
+    ($slice:expr, [[$t:ty ; $m:expr] ; $n:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_2d_array_rows_mut::<$t, { $m }, { $n }>(s)
+    }}
+}
+
+/// Reinterpret a flat slice as a slice of `[T; N]`, for random access by chunk index.
+/// Returns `None` if the slice's length is not a multiple of `N`, or if `N` is 0.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_slice<T, const N: usize>(slice: &[T]) -> Option<&[[T; N]]> {
+    if !check::divides_evenly(slice, N) {
+        report_mismatch::<T>(N, Mismatch::NotMultiple(slice.len()));
+        return None;
+    }
+    let ptr = slice.as_ptr() as *const [T; N];
+    reexport::debug_assert_aligned(ptr);
+    Some(unsafe { slice::from_raw_parts(ptr, slice.len() / N) })
+}
+
+/// Reinterpret a flat mutable slice as a mutable slice of `[T; N]`.
+/// Returns `None` if the slice's length is not a multiple of `N`, or if `N` is 0.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_slice_mut<T, const N: usize>(slice: &mut [T]) -> Option<&mut [[T; N]]> {
+    if !check::divides_evenly(slice, N) {
+        report_mismatch::<T>(N, Mismatch::NotMultiple(slice.len()));
+        return None;
+    }
+    let ptr = slice.as_mut_ptr() as *mut [T; N];
+    reexport::debug_assert_aligned(ptr);
+    Some(unsafe { slice::from_raw_parts_mut(ptr, slice.len() / N) })
+}
+
+/// Reinterpret a flat slice as a slice of arrays.
+/// `slice_as_array_slice!(slice, [element_type; chunk_length]) -> Option<&[[element_type; chunk_length]]>`
+#[macro_export]
+macro_rules! slice_as_array_slice {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_slice::<$t, { $len }>(s)
+    }}
+}
+
+/// Reinterpret a flat mutable slice as a mutable slice of arrays.
+/// `slice_as_array_slice_mut!(mutable_slice, [element_type; chunk_length]) -> Option<&mut [[element_type; chunk_length]]>`
+#[macro_export]
+macro_rules! slice_as_array_slice_mut {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_slice_mut::<$t, { $len }>(s)
+    }}
+}
+
+/// Borrow row `row_index` of a flat slice of rows of `row_len` elements as an array.
+/// Returns `None` if `row_len` is not `N`, or if the row is not entirely
+/// within the slice, including when its start overflows.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn row_as_array<T, const N: usize>(flat: &[T], row_len: usize, row_index: usize) -> Option<&[T; N]> {
+    if row_len != N {
+        report_length_mismatch::<T>(N, row_len);
+        return None;
+    }
+    match row_index.checked_mul(row_len) {
+        Some(offset) => slice_as_array_at(flat, offset),
+        None => {
+            report_mismatch::<T>(N, Mismatch::RowOverflow { row: row_index, row_len });
+            None
+        }
+    }
+}
+
+/// Mutably borrow row `row_index` of a flat slice of rows of `row_len` elements as an array.
+/// Returns `None` if `row_len` is not `N`, or if the row is not entirely
+/// within the slice, including when its start overflows.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn row_as_array_mut<T, const N: usize>(flat: &mut [T], row_len: usize, row_index: usize) -> Option<&mut [T; N]> {
+    if row_len != N {
+        report_length_mismatch::<T>(N, row_len);
+        return None;
+    }
+    match row_index.checked_mul(row_len) {
+        Some(offset) => slice_as_array_at_mut(flat, offset),
+        None => {
+            report_mismatch::<T>(N, Mismatch::RowOverflow { row: row_index, row_len });
+            None
+        }
+    }
+}
+
+/// Borrow one row of a flat slice with a runtime row length as an array.
+/// `row_as_array!(flat_slice, row_len, row_index, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! row_as_array {
+    ($flat:expr, $row_len:expr, $row_index:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($flat).slice_arg_ref());
+        $crate::row_as_array::<$t, { $len }>(s, $row_len, $row_index)
+    }}
+}
+
+/// Mutably borrow one row of a flat slice with a runtime row length as an array.
+/// `row_as_array_mut!(flat_mutable_slice, row_len, row_index, [element_type; array_length]) -> Option<&mut [element_type; array_length]>`
+#[macro_export]
+macro_rules! row_as_array_mut {
+    ($flat:expr, $row_len:expr, $row_index:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($flat);
+        $crate::row_as_array_mut::<$t, { $len }>(s, $row_len, $row_index)
+    }}
+}
+
+/// View `N` rows of `M` elements as one flat slice of `N * M` elements.
+///
+/// # Panics
+///
+/// Panics if `N * M` overflows, which is only possible for zero-sized `T`.
+#[inline]
+pub fn array_flatten<T, const M: usize, const N: usize>(array: &[[T; M]; N]) -> &[T] {
+    let len = N.checked_mul(M).expect("flattened length overflows usize");
+    unsafe { slice::from_raw_parts(array.as_ptr() as *const T, len) }
+}
+
+/// Mutably view `N` rows of `M` elements as one flat slice of `N * M` elements.
+///
+/// # Panics
+///
+/// Panics if `N * M` overflows, which is only possible for zero-sized `T`.
+#[inline]
+pub fn array_flatten_mut<T, const M: usize, const N: usize>(array: &mut [[T; M]; N]) -> &mut [T] {
+    let len = N.checked_mul(M).expect("flattened length overflows usize");
+    unsafe { slice::from_raw_parts_mut(array.as_mut_ptr() as *mut T, len) }
+}
+
+/// View `N` rows of `M` elements as one flat array of `M * N` elements.
+/// This needs a nightly toolchain and the `generic_const_exprs` feature.
+#[cfg(feature="generic_const_exprs")]
+#[inline]
+pub fn array_flatten_to_array<T, const M: usize, const N: usize>(array: &[[T; M]; N]) -> &[T; M * N] where [(); M * N]: {
+    unsafe { &*(array as *const [[T; M]; N] as *const [T; M * N]) }
+}
+
+/// Mutably view `N` rows of `M` elements as one flat array of `M * N` elements.
+/// This needs a nightly toolchain and the `generic_const_exprs` feature.
+#[cfg(feature="generic_const_exprs")]
+#[inline]
+pub fn array_flatten_to_array_mut<T, const M: usize, const N: usize>(array: &mut [[T; M]; N]) -> &mut [T; M * N] where [(); M * N]: {
+    unsafe { &mut *(array as *mut [[T; M]; N] as *mut [T; M * N]) }
+}
+
+/// View a two-dimensional array as a flat slice in row-major order.
+/// `array_flatten!(&[[element_type; row_length]; row_count]) -> &[element_type]`
+#[macro_export]
+macro_rules! array_flatten {
+    ($array:expr) => {{
+        $crate::array_flatten($array)
+    }}
+}
+
+/// Mutably view a two-dimensional array as a flat slice in row-major order.
+/// `array_flatten_mut!(&mut [[element_type; row_length]; row_count]) -> &mut [element_type]`
+#[macro_export]
+macro_rules! array_flatten_mut {
+    ($array:expr) => {{
+        $crate::array_flatten_mut($array)
+    }}
+}
+
+struct AssertProduct<const M: usize, const N: usize, const K: usize>;
+
+impl<const M: usize, const N: usize, const K: usize> AssertProduct<M, N, K> {
+    // Evaluated, and so checked, when the function using it is monomorphized.
+    const HOLDS: () = assert!(matches!(M.checked_mul(N), Some(product) if product == K), "array lengths do not match");
+}
+
+/// Reinterpret a flat array of `K` elements as `N` rows of `M` elements.
+/// It is a compile error for `K` to be anything other than `M * N`.
+#[inline]
+pub fn array_reshape<T, const M: usize, const N: usize, const K: usize>(array: &[T; K]) -> &[[T; M]; N] {
+    let () = AssertProduct::<M, N, K>::HOLDS;
+    unsafe { &*(array as *const [T; K] as *const [[T; M]; N]) }
+}
+
+/// Mutably reinterpret a flat array of `K` elements as `N` rows of `M` elements.
+/// It is a compile error for `K` to be anything other than `M * N`.
+#[inline]
+pub fn array_reshape_mut<T, const M: usize, const N: usize, const K: usize>(array: &mut [T; K]) -> &mut [[T; M]; N] {
+    let () = AssertProduct::<M, N, K>::HOLDS;
+    unsafe { &mut *(array as *mut [T; K] as *mut [[T; M]; N]) }
+}
+
+/// Move a flat array of `K` elements into `N` rows of `M` elements.
+/// It is a compile error for `K` to be anything other than `M * N`.
+#[inline]
+pub fn array_reshape_owned<T, const M: usize, const N: usize, const K: usize>(array: [T; K]) -> [[T; M]; N] {
+    let () = AssertProduct::<M, N, K>::HOLDS;
+    // The elements are moved into the result, so `array` must not drop them.
+    let array = ManuallyDrop::new(array);
+    unsafe { reexport::ptr_read(&*array as *const [T; K] as *const [[T; M]; N]) }
+}
+
+/// Reinterpret a flat array as a two-dimensional array in row-major order.
+/// `array_reshape!(&[element_type; row_length * row_count], [[element_type; row_length]; row_count]) -> &[[element_type; row_length]; row_count]`
+#[macro_export]
+macro_rules! array_reshape {
+    ($array:expr, [[$t:ty ; $m:expr] ; $n:expr] ) => {{
+        let rows: &[[$t; $m]; $n] = $crate::array_reshape($array);
+        rows
+    }}
+}
+
+/// Mutably reinterpret a flat array as a two-dimensional array in row-major order.
+/// `array_reshape_mut!(&mut [element_type; row_length * row_count], [[element_type; row_length]; row_count]) -> &mut [[element_type; row_length]; row_count]`
+#[macro_export]
+macro_rules! array_reshape_mut {
+    ($array:expr, [[$t:ty ; $m:expr] ; $n:expr] ) => {{
+        let rows: &mut [[$t; $m]; $n] = $crate::array_reshape_mut($array);
+        rows
+    }}
+}
+
+/// Move a flat array into a two-dimensional array in row-major order.
+/// `array_reshape_owned!([element_type; row_length * row_count], [[element_type; row_length]; row_count]) -> [[element_type; row_length]; row_count]`
+#[macro_export]
+macro_rules! array_reshape_owned {
+    ($array:expr, [[$t:ty ; $m:expr] ; $n:expr] ) => {{
+        let rows: [[$t; $m]; $n] = $crate::array_reshape_owned($array);
+        rows
+    }}
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn as_2d_array() {
+        let xs: [u8; 12] = [0, 1, 2, 3, 10, 11, 12, 13, 20, 21, 22, 23];
+        let rows: &[[u8; 4]; 3] = slice_as_2d_array!(&xs[..], [[u8; 4]; 3]).unwrap();
+        for (row, values) in rows.iter().enumerate() {
+            for (col, &value) in values.iter().enumerate() {
+                assert_eq!(value as usize, row * 10 + col);
+            }
+        }
+    }
+
+    #[test]
+    fn as_2d_array_wrong_length() {
+        let xs: [u8; 12] = [0; 12];
+        assert_eq!(slice_as_2d_array!(&xs[..11], [[u8; 4]; 3]), None);
+        assert_eq!(slice_as_2d_array!(&xs[..], [[u8; 3]; 3]), None);
+    }
+
+    #[test]
+    fn as_2d_array_mut() {
+        let mut xs: [u8; 6] = [0; 6];
+        slice_as_2d_array_mut!(&mut xs[..], [[u8; 3]; 2]).unwrap()[1][0] = 5;
+        assert_eq!(xs, [0, 0, 0, 5, 0, 0]);
+        assert_eq!(slice_as_2d_array_mut!(&mut xs[..], [[u8; 3]; 3]), None);
+    }
+
+    #[test]
+    fn flatten() {
+        let rows: [[u8; 2]; 3] = [[1, 2], [3, 4], [5, 6]];
+        assert_eq!(array_flatten!(&rows), &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn flatten_round_trip() {
+        let xs: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        let rows = slice_as_2d_array!(&xs[..], [[u8; 3]; 2]).unwrap();
+        assert_eq!(array_flatten!(rows), &xs[..]);
+    }
+
+    #[test]
+    fn flatten_mut() {
+        let mut rows: [[u8; 2]; 2] = [[1, 2], [3, 4]];
+        array_flatten_mut!(&mut rows)[2] = 9;
+        assert_eq!(rows, [[1, 2], [9, 4]]);
+    }
+
+    #[test]
+    #[cfg(feature="generic_const_exprs")]
+    fn flatten_to_array() {
+        let mut rows: [[u8; 2]; 3] = [[1, 2], [3, 4], [5, 6]];
+        let flat: &[u8; 6] = super::array_flatten_to_array(&rows);
+        assert_eq!(flat, &[1, 2, 3, 4, 5, 6]);
+        super::array_flatten_to_array_mut(&mut rows)[5] = 0;
+        assert_eq!(rows[2], [5, 0]);
+    }
+
+    #[test]
+    fn reshape() {
+        let xs: [u8; 6] = [0, 1, 2, 10, 11, 12];
+        assert_eq!(array_reshape!(&xs, [[u8; 3]; 2]), &[[0, 1, 2], [10, 11, 12]]);
+        assert_eq!(array_reshape!(&xs, [[u8; 2]; 3]), &[[0, 1], [2, 10], [11, 12]]);
+    }
+
+    #[test]
+    fn reshape_mut() {
+        let mut xs: [u8; 6] = [0; 6];
+        array_reshape_mut!(&mut xs, [[u8; 2]; 3])[1][1] = 5;
+        assert_eq!(xs, [0, 0, 0, 5, 0, 0]);
+    }
+
+    #[test]
+    fn reshape_owned() {
+        let xs: [String; 4] = ["a".into(), "b".into(), "c".into(), "d".into()];
+        let rows = array_reshape_owned!(xs, [[String; 2]; 2]);
+        assert_eq!(rows[1][0], "c");
+    }
+
+    #[test]
+    fn rows_mut() {
+        let mut xs: [u8; 6] = [0; 6];
+        let (first, second) = {
+            let mut rows = slice_as_2d_array_rows_mut!(&mut xs[..], [[u8; 3]; 2]).unwrap();
+            (rows.next().unwrap(), rows.next().unwrap())
+        };
+        // Both rows are borrowed mutably at once.
+        first[0] = 1;
+        second[2] = 2;
+        assert_eq!(xs, [1, 0, 0, 0, 0, 2]);
+        assert!(slice_as_2d_array_rows_mut!(&mut xs[..5], [[u8; 3]; 2]).is_none());
+    }
+
+    #[test]
+    fn row() {
+        let flat: [u8; 7] = [1, 2, 3, 4, 5, 6, 7];
+        let row_len = 3;
+        assert_eq!(row_as_array!(&flat[..], row_len, 1, [u8; 3]), Some(&[4, 5, 6]));
+        assert_eq!(row_as_array!(&flat[..], row_len, 2, [u8; 3]), None);
+        assert_eq!(row_as_array!(&flat[..], 2, 0, [u8; 3]), None);
+        let mut flat = flat;
+        row_as_array_mut!(&mut flat[..], 2, 2, [u8; 2]).unwrap()[0] = 0;
+        assert_eq!(flat, [1, 2, 3, 4, 0, 6, 7]);
+    }
+
+    #[test]
+    fn row_start_overflows() {
+        let flat: [u8; 4] = [1, 2, 3, 4];
+        // `usize::MAX / 2 * 2` does not overflow, but `usize::MAX / 2 * 3` does.
+        assert_eq!(row_as_array!(&flat[..], 2, usize::MAX / 2, [u8; 2]), None);
+        assert_eq!(row_as_array!(&flat[..], 3, usize::MAX / 2, [u8; 3]), None);
+        let mut flat = flat;
+        assert!(row_as_array_mut!(&mut flat[..], 4, usize::MAX, [u8; 4]).is_none());
+    }
+
+    #[test]
+    fn array_slice() {
+        let xs = [1u16, 2, 3, 4, 5, 6];
+        let pairs = slice_as_array_slice!(&xs, [u16; 2]).unwrap();
+        assert_eq!(pairs, &[[1, 2], [3, 4], [5, 6]]);
+        assert_eq!(pairs[2], [5, 6]);
+        assert_eq!(slice_as_array_slice!(&xs, [u16; 3]).map(<[_]>::len), Some(2));
+        assert!(slice_as_array_slice!(&xs, [u16; 4]).is_none());
+        assert!(slice_as_array_slice!(&xs, [u16; 0]).is_none());
+        let empty: &[u16] = &[];
+        assert_eq!(slice_as_array_slice!(empty, [u16; 4]), Some(&[][..]));
+    }
+
+    #[test]
+    fn array_slice_mut() {
+        let mut xs = [1u8, 2, 3, 4];
+        {
+            let pairs = slice_as_array_slice_mut!(&mut xs, [u8; 2]).unwrap();
+            pairs.swap(0, 1);
+            pairs[1][1] = 9;
+        }
+        assert_eq!(xs, [3, 4, 1, 9]);
+        assert!(slice_as_array_slice_mut!(&mut xs, [u8; 3]).is_none());
+        assert_eq!(slice_as_array_slice_mut!(&mut [][..], [u8; 4]).map(|s| s.len()), Some(0));
+    }
+}
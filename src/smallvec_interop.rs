@@ -0,0 +1,74 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions from `smallvec::SmallVec`, behind the `smallvec` feature.
+
+use smallvec::{Array, SmallVec};
+use {check, reexport, report_length_mismatch};
+
+/// Move the elements of a `SmallVec` into an array without cloning them.
+/// Returns the `SmallVec` unchanged if it does not have exactly `N` elements.
+/// Works whether the elements are inline or spilled to the heap, freeing the
+/// heap buffer in the latter case.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn smallvec_into_array<A: Array, const N: usize>(mut vec: SmallVec<A>) -> Result<[A::Item; N], SmallVec<A>> {
+    if !check::has_len(&vec, N) {
+        report_length_mismatch::<A::Item>(N, vec.len());
+        return Err(vec);
+    }
+    let mut array = reexport::MaybeUninit::<[A::Item; N]>::uninit();
+    unsafe {
+        // The elements now belong to `array`, so `vec` must only free its buffer.
+        vec.set_len(0);
+        reexport::copy_nonoverlapping(vec.as_ptr(), array.as_mut_ptr() as *mut A::Item, N);
+        Ok(array.assume_init())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+    use smallvec::SmallVec;
+    use super::smallvec_into_array;
+
+    #[test]
+    fn inline() {
+        let xs: SmallVec<[u32; 4]> = SmallVec::from_slice(&[1, 2, 3]);
+        assert!(!xs.spilled());
+        assert_eq!(smallvec_into_array(xs), Ok([1, 2, 3]));
+    }
+
+    #[test]
+    fn spilled() {
+        let xs: SmallVec<[u32; 2]> = SmallVec::from_slice(&[1, 2, 3]);
+        assert!(xs.spilled());
+        assert_eq!(smallvec_into_array(xs), Ok([1, 2, 3]));
+    }
+
+    #[test]
+    fn wrong_length() {
+        let xs: SmallVec<[u32; 2]> = SmallVec::from_slice(&[1, 2, 3]);
+        let xs = smallvec_into_array::<_, 2>(xs).unwrap_err();
+        assert_eq!(&xs[..], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn moves() {
+        // One vector is inline and the other spilled; neither may clone or drop.
+        let shared = Rc::new(5);
+        let inline: SmallVec<[Rc<u32>; 2]> = (0..1).map(|_| shared.clone()).collect();
+        let spilled: SmallVec<[Rc<u32>; 2]> = (0..3).map(|_| shared.clone()).collect();
+        let a: [Rc<u32>; 1] = smallvec_into_array(inline).unwrap();
+        let b: [Rc<u32>; 3] = smallvec_into_array(spilled).unwrap();
+        assert_eq!(Rc::strong_count(&shared), 5);
+        drop((a, b));
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+}
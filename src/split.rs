@@ -0,0 +1,914 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions that borrow only part of a slice as an array.
+
+#[cfg(feature="use_std")]
+use std::{cmp, ops::Range};
+#[cfg(not(feature="use_std"))]
+use core::{cmp, ops::Range};
+
+use {check, report_mismatch, slice_as_array, slice_as_array_mut, Mismatch};
+
+// Whether `len` elements starting at `offset` lie within the slice. If not,
+// the range they would need is reported.
+#[inline]
+#[track_caller]
+fn fits<T>(slice: &[T], offset: usize, len: usize) -> bool {
+    match slice.len().checked_sub(offset) {
+        Some(available) if available >= len => true,
+        _ => {
+            report_mismatch::<T>(len, Mismatch::OutOfBounds { start: offset, end: offset.saturating_add(len), len: slice.len() });
+            false
+        }
+    }
+}
+
+/// Borrow the first `N` elements of a slice as an array.
+/// Returns `None` if the slice has fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_prefix<T, const N: usize>(slice: &[T]) -> Option<&[T; N]> {
+    if !check::has_at_least(slice, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(slice.len()));
+        return None;
+    }
+    slice_as_array(&slice[..N])
+}
+
+/// Mutably borrow the first `N` elements of a slice as an array.
+/// Returns `None` if the slice has fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_prefix_mut<T, const N: usize>(slice: &mut [T]) -> Option<&mut [T; N]> {
+    if !check::has_at_least(slice, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(slice.len()));
+        return None;
+    }
+    slice_as_array_mut(&mut slice[..N])
+}
+
+/// Borrow the first elements of a slice as an array, ignoring the rest.
+/// `slice_as_array_prefix!(slice, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_array_prefix {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_prefix::<$t, { $len }>(s)
+    }}
+}
+
+/// Mutably borrow the first elements of a slice as an array, ignoring the rest.
+/// `slice_as_array_prefix_mut!(mutable_slice, [element_type; array_length]) -> Option<&mut [element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_array_prefix_mut {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_prefix_mut::<$t, { $len }>(s)
+    }}
+}
+
+/// Borrow the last `N` elements of a slice as an array.
+/// Returns `None` if the slice has fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_suffix<T, const N: usize>(slice: &[T]) -> Option<&[T; N]> {
+    if !check::has_at_least(slice, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(slice.len()));
+        return None;
+    }
+    let start = slice.len() - N;
+    slice_as_array(&slice[start..])
+}
+
+/// Mutably borrow the last `N` elements of a slice as an array.
+/// Returns `None` if the slice has fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_suffix_mut<T, const N: usize>(slice: &mut [T]) -> Option<&mut [T; N]> {
+    if !check::has_at_least(slice, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(slice.len()));
+        return None;
+    }
+    let start = slice.len() - N;
+    slice_as_array_mut(&mut slice[start..])
+}
+
+/// Borrow the last elements of a slice as an array, ignoring the rest.
+/// `slice_as_array_suffix!(slice, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_array_suffix {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_suffix::<$t, { $len }>(s)
+    }}
+}
+
+/// Mutably borrow the last elements of a slice as an array, ignoring the rest.
+/// `slice_as_array_suffix_mut!(mutable_slice, [element_type; array_length]) -> Option<&mut [element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_array_suffix_mut {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_suffix_mut::<$t, { $len }>(s)
+    }}
+}
+
+/// Split a slice into an array of its first `N` elements and the remaining slice.
+/// Returns `None` if the slice has fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_split_array<T, const N: usize>(slice: &[T]) -> Option<(&[T; N], &[T])> {
+    if !check::has_at_least(slice, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(slice.len()));
+        return None;
+    }
+    let (head, tail) = slice.split_at(N);
+    slice_as_array(head).map(|head| (head, tail))
+}
+
+/// Split a mutable slice into an array of its first `N` elements and the remaining slice.
+/// Returns `None` if the slice has fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_split_array_mut<T, const N: usize>(slice: &mut [T]) -> Option<(&mut [T; N], &mut [T])> {
+    if !check::has_at_least(slice, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(slice.len()));
+        return None;
+    }
+    let (head, tail) = slice.split_at_mut(N);
+    slice_as_array_mut(head).map(|head| (head, tail))
+}
+
+/// Split a slice into a fixed-size head array and the remaining slice.
+/// `slice_split_array!(slice, [element_type; array_length]) -> Option<(&[element_type; array_length], &[element_type])>`
+#[macro_export]
+macro_rules! slice_split_array {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_split_array::<$t, { $len }>(s)
+    }}
+}
+
+/// Split a mutable slice into a fixed-size head array and the remaining slice.
+/// `slice_split_array_mut!(mutable_slice, [element_type; array_length]) -> Option<(&mut [element_type; array_length], &mut [element_type])>`
+#[macro_export]
+macro_rules! slice_split_array_mut {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_split_array_mut::<$t, { $len }>(s)
+    }}
+}
+
+/// Clone the first `N` elements of a slice into an array, and borrow the rest.
+/// Returns `None` if the slice has fewer than `N` elements. If a clone
+/// panics, the elements cloned so far are dropped.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_clone_split<T: Clone, const N: usize>(slice: &[T]) -> Option<([T; N], &[T])> {
+    let (head, tail) = slice_split_array(slice)?;
+    Some((head.clone(), tail))
+}
+
+/// Clone a fixed-size head of a slice into an array, and borrow the remaining slice.
+/// `slice_clone_split!(slice, [element_type; array_length]) -> Option<([element_type; array_length], &[element_type])>`
+#[macro_export]
+macro_rules! slice_clone_split {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_clone_split::<$t, { $len }>(s)
+    }}
+}
+
+/// Split a slice into the leading slice and an array of its last `N` elements.
+/// Returns `None` if the slice has fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_rsplit_array<T, const N: usize>(slice: &[T]) -> Option<(&[T], &[T; N])> {
+    if !check::has_at_least(slice, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(slice.len()));
+        return None;
+    }
+    let (head, tail) = slice.split_at(slice.len() - N);
+    slice_as_array(tail).map(|tail| (head, tail))
+}
+
+/// Split a mutable slice into the leading slice and an array of its last `N` elements.
+/// Returns `None` if the slice has fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_rsplit_array_mut<T, const N: usize>(slice: &mut [T]) -> Option<(&mut [T], &mut [T; N])> {
+    if !check::has_at_least(slice, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(slice.len()));
+        return None;
+    }
+    let (head, tail) = slice.split_at_mut(slice.len() - N);
+    slice_as_array_mut(tail).map(|tail| (head, tail))
+}
+
+/// Split a slice into the leading slice and a fixed-size tail array.
+/// `slice_rsplit_array!(slice, [element_type; array_length]) -> Option<(&[element_type], &[element_type; array_length])>`
+#[macro_export]
+macro_rules! slice_rsplit_array {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_rsplit_array::<$t, { $len }>(s)
+    }}
+}
+
+/// Split a mutable slice into the leading slice and a fixed-size tail array.
+/// `slice_rsplit_array_mut!(mutable_slice, [element_type; array_length]) -> Option<(&mut [element_type], &mut [element_type; array_length])>`
+#[macro_export]
+macro_rules! slice_rsplit_array_mut {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_rsplit_array_mut::<$t, { $len }>(s)
+    }}
+}
+
+/// Borrow the `N` elements starting at `offset` as an array.
+/// Returns `None` if they do not all lie within the slice, including when
+/// `offset + N` overflows.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_at<T, const N: usize>(slice: &[T], offset: usize) -> Option<&[T; N]> {
+    if !fits(slice, offset, N) {
+        return None;
+    }
+    slice_as_array(&slice[offset..offset + N])
+}
+
+/// Mutably borrow the `N` elements starting at `offset` as an array.
+/// Returns `None` if they do not all lie within the slice, including when
+/// `offset + N` overflows.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_at_mut<T, const N: usize>(slice: &mut [T], offset: usize) -> Option<&mut [T; N]> {
+    if !fits(slice, offset, N) {
+        return None;
+    }
+    slice_as_array_mut(&mut slice[offset..offset + N])
+}
+
+/// Borrow `N` elements as an array, starting at `offset` or as close to it
+/// as the slice allows.
+/// If `offset + N` is past the end of the slice, the offset is clamped down
+/// to `len - N`, so the last `N` elements are returned; the array never ends
+/// later than `offset + N` would. Returns `None` only if the slice has fewer
+/// than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_at_saturating<T, const N: usize>(slice: &[T], offset: usize) -> Option<&[T; N]> {
+    if !check::has_at_least(slice, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(slice.len()));
+        return None;
+    }
+    slice_as_array_at(slice, cmp::min(offset, slice.len() - N))
+}
+
+/// Mutably borrow `N` elements as an array, starting at `offset` or as close
+/// to it as the slice allows.
+/// The offset is clamped the same way as in `slice_as_array_at_saturating`.
+/// Returns `None` only if the slice has fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_at_saturating_mut<T, const N: usize>(slice: &mut [T], offset: usize) -> Option<&mut [T; N]> {
+    if !check::has_at_least(slice, N) {
+        report_mismatch::<T>(N, Mismatch::TooShort(slice.len()));
+        return None;
+    }
+    slice_as_array_at_mut(slice, cmp::min(offset, slice.len() - N))
+}
+
+/// Borrow the elements in `range` as an array.
+/// Returns `None` if the range is out of bounds or backwards, or does not
+/// hold exactly `N` elements. Unlike indexing first, this never panics.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_as_array_range<T, const N: usize>(slice: &[T], range: Range<usize>) -> Option<&[T; N]> {
+    match slice.get(range.clone()) {
+        Some(elements) => slice_as_array(elements),
+        None => {
+            report_mismatch::<T>(N, Mismatch::OutOfBounds { start: range.start, end: range.end, len: slice.len() });
+            None
+        }
+    }
+}
+
+/// Split a slice into the elements before `mid`, an array of the `N` elements
+/// starting at `mid`, and the elements after those.
+/// Returns `None` if `mid + N` is past the end of the slice, or overflows.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_split_mid_array<T, const N: usize>(slice: &[T], mid: usize) -> Option<(&[T], &[T; N], &[T])> {
+    if !fits(slice, mid, N) {
+        return None;
+    }
+    let (prefix, rest) = slice.split_at(mid);
+    let (array, suffix) = slice_split_array(rest)?;
+    Some((prefix, array, suffix))
+}
+
+/// Split a mutable slice into the elements before `mid`, an array of the `N`
+/// elements starting at `mid`, and the elements after those.
+/// Returns `None` if `mid + N` is past the end of the slice, or overflows.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_split_mid_array_mut<T, const N: usize>(slice: &mut [T], mid: usize) -> Option<(&mut [T], &mut [T; N], &mut [T])> {
+    if !fits(slice, mid, N) {
+        return None;
+    }
+    let (prefix, rest) = slice.split_at_mut(mid);
+    let (array, suffix) = slice_split_array_mut(rest)?;
+    Some((prefix, array, suffix))
+}
+
+/// Mutably borrow `A` elements starting at `a_offset` and `B` elements
+/// starting at `b_offset` as two arrays at once.
+/// Returns `None` if either range is out of bounds or the two overlap. An
+/// empty array holds no elements, so it never overlaps the other.
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn slice_two_arrays_mut<T, const A: usize, const B: usize>(slice: &mut [T], a_offset: usize, b_offset: usize) -> Option<(&mut [T; A], &mut [T; B])> {
+    if !fits(slice, a_offset, A) || !fits(slice, b_offset, B) {
+        return None;
+    }
+    let (a_end, b_end) = (a_offset + A, b_offset + B);
+    // Splitting between the ranges makes the borrows disjoint by construction.
+    if a_end <= b_offset {
+        let (left, right) = slice.split_at_mut(b_offset);
+        Some((slice_as_array_at_mut(left, a_offset)?, slice_as_array_prefix_mut(right)?))
+    } else if b_end <= a_offset {
+        let (left, right) = slice.split_at_mut(a_offset);
+        Some((slice_as_array_prefix_mut(right)?, slice_as_array_at_mut(left, b_offset)?))
+    } else if A == 0 {
+        Some((slice_as_array_mut(&mut [])?, slice_as_array_at_mut(slice, b_offset)?))
+    } else if B == 0 {
+        Some((slice_as_array_at_mut(slice, a_offset)?, slice_as_array_mut(&mut [])?))
+    } else {
+        report_mismatch::<T>(A, Mismatch::Overlapping { offset: a_offset, other_offset: b_offset, other_len: B });
+        None
+    }
+}
+
+/// Borrow the `N` elements following `prefix` as an array.
+/// Returns `None` if the slice does not start with `prefix`, or if it does
+/// not have exactly `N` elements after it.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn strip_prefix_as_array<'a, T: PartialEq, const N: usize>(slice: &'a [T], prefix: &[T]) -> Option<&'a [T; N]> {
+    match slice.strip_prefix(prefix) {
+        Some(rest) => slice_as_array(rest),
+        None => {
+            report_mismatch::<T>(N, Mismatch::WrongPrefix(prefix.len()));
+            None
+        }
+    }
+}
+
+/// Borrow two slices as arrays of the same length `N`.
+/// Returns `None` unless both slices have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn zip_slices_as_arrays<'a, 'b, T, U, const N: usize>(a: &'a [T], b: &'b [U]) -> Option<(&'a [T; N], &'b [U; N])> {
+    Some((slice_as_array(a)?, slice_as_array(b)?))
+}
+
+/// Mutably borrow two slices as arrays of the same length `N`.
+/// Returns `None` unless both slices have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn zip_slices_as_arrays_mut<'a, 'b, T, U, const N: usize>(a: &'a mut [T], b: &'b mut [U]) -> Option<(&'a mut [T; N], &'b mut [U; N])> {
+    Some((slice_as_array_mut(a)?, slice_as_array_mut(b)?))
+}
+
+/// Borrow the elements starting at an offset as an array, without panicking if they are out of bounds.
+/// `slice_as_array_at!(slice, offset, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_array_at {
+    ($slice:expr, $offset:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_at::<$t, { $len }>(s, $offset)
+    }}
+}
+
+/// Mutably borrow the elements starting at an offset as an array, without panicking if they are out of bounds.
+/// `slice_as_array_at_mut!(mutable_slice, offset, [element_type; array_length]) -> Option<&mut [element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_array_at_mut {
+    ($slice:expr, $offset:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_at_mut::<$t, { $len }>(s, $offset)
+    }}
+}
+
+/// Borrow the elements starting at an offset as an array, clamping the offset so they fit.
+/// `slice_as_array_at_saturating!(slice, offset, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_array_at_saturating {
+    ($slice:expr, $offset:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_at_saturating::<$t, { $len }>(s, $offset)
+    }}
+}
+
+/// Mutably borrow the elements starting at an offset as an array, clamping the offset so they fit.
+/// `slice_as_array_at_saturating_mut!(mutable_slice, offset, [element_type; array_length]) -> Option<&mut [element_type; array_length]>`
+#[macro_export]
+macro_rules! slice_as_array_at_saturating_mut {
+    ($slice:expr, $offset:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_at_saturating_mut::<$t, { $len }>(s, $offset)
+    }}
+}
+
+/// Split a slice around an array starting at `mid`, keeping both remainders.
+/// `slice_split_mid_array!(slice, mid, [element_type; array_length]) -> Option<(&[element_type], &[element_type; array_length], &[element_type])>`
+#[macro_export]
+macro_rules! slice_split_mid_array {
+    ($slice:expr, $mid:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_split_mid_array::<$t, { $len }>(s, $mid)
+    }}
+}
+
+/// Split a mutable slice around an array starting at `mid`, keeping both remainders.
+/// `slice_split_mid_array_mut!(mutable_slice, mid, [element_type; array_length]) -> Option<(&mut [element_type], &mut [element_type; array_length], &mut [element_type])>`
+#[macro_export]
+macro_rules! slice_split_mid_array_mut {
+    ($slice:expr, $mid:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_split_mid_array_mut::<$t, { $len }>(s, $mid)
+    }}
+}
+
+/// Mutably borrow two non-overlapping runs of a slice as arrays.
+/// `slice_two_arrays_mut!(mutable_slice, (a_offset, [element_type; a_length]), (b_offset, [element_type; b_length])) -> Option<(&mut [element_type; a_length], &mut [element_type; b_length])>`
+#[macro_export]
+macro_rules! slice_two_arrays_mut {
+    ($slice:expr, ($a_offset:expr, [$t:ty ; $a:expr]), ($b_offset:expr, [$u:ty ; $b:expr]) ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        let arrays: Option<(&mut [$t; $a], &mut [$u; $b])> = $crate::slice_two_arrays_mut(s, $a_offset, $b_offset);
+        arrays
+    }}
+}
+
+/// Check a slice's prefix and borrow the elements after it as an array.
+/// `strip_prefix_as_array!(slice, prefix, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! strip_prefix_as_array {
+    ($slice:expr, $prefix:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        let prefix: &[$t] = $crate::reexport::slice_arg(($prefix).slice_arg_ref());
+        $crate::strip_prefix_as_array::<$t, { $len }>(s, prefix)
+    }}
+}
+
+/// Borrow two slices as arrays, which must both have the given length.
+/// `zip_slices_as_arrays!(a, b, [a_element_type; length], [b_element_type; length]) -> Option<(&[a_element_type; length], &[b_element_type; length])>`
+/// It is a compile error for the two lengths to differ.
+#[macro_export]
+macro_rules! zip_slices_as_arrays {
+    ($a:expr, $b:expr, [$t:ty ; $n:expr], [$u:ty ; $m:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let a: &[$t] = $crate::reexport::slice_arg(($a).slice_arg_ref());
+        let b: &[$u] = $crate::reexport::slice_arg(($b).slice_arg_ref());
+        let arrays: Option<(&[$t; $n], &[$u; $m])> = $crate::zip_slices_as_arrays(a, b);
+        arrays
+    }}
+}
+
+/// Mutably borrow two slices as arrays, which must both have the given length.
+/// `zip_slices_as_arrays_mut!(a, b, [a_element_type; length], [b_element_type; length]) -> Option<(&mut [a_element_type; length], &mut [b_element_type; length])>`
+/// It is a compile error for the two lengths to differ.
+#[macro_export]
+macro_rules! zip_slices_as_arrays_mut {
+    ($a:expr, $b:expr, [$t:ty ; $n:expr], [$u:ty ; $m:expr] ) => {{
+        let a: &mut [$t] = $crate::reexport::as_mut_slice_arg($a);
+        let b: &mut [$u] = $crate::reexport::as_mut_slice_arg($b);
+        let arrays: Option<(&mut [$t; $n], &mut [$u; $m])> = $crate::zip_slices_as_arrays_mut(a, b);
+        arrays
+    }}
+}
+
+/// Borrow the first elements of a slice as an array. This is another name for
+/// `slice_as_array_prefix!`; use `slice_split_array!` to also get the rest of the slice.
+/// `first_array!(slice, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! first_array {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_prefix::<$t, { $len }>(s)
+    }}
+}
+
+/// Mutably borrow the first elements of a slice as an array. This is another name for
+/// `slice_as_array_prefix_mut!`; use `slice_split_array_mut!` to also get the rest of the slice.
+/// `first_array_mut!(mutable_slice, [element_type; array_length]) -> Option<&mut [element_type; array_length]>`
+#[macro_export]
+macro_rules! first_array_mut {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_prefix_mut::<$t, { $len }>(s)
+    }}
+}
+
+/// Borrow the last elements of a slice as an array. This is another name for
+/// `slice_as_array_suffix!`; use `slice_rsplit_array!` to also get the rest of the slice.
+/// `last_array!(slice, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! last_array {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_suffix::<$t, { $len }>(s)
+    }}
+}
+
+/// Mutably borrow the last elements of a slice as an array. This is another name for
+/// `slice_as_array_suffix_mut!`; use `slice_rsplit_array_mut!` to also get the rest of the slice.
+/// `last_array_mut!(mutable_slice, [element_type; array_length]) -> Option<&mut [element_type; array_length]>`
+#[macro_export]
+macro_rules! last_array_mut {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$t] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::slice_as_array_suffix_mut::<$t, { $len }>(s)
+    }}
+}
+
+/// Borrow the first elements of a base slice as an array, returning `None`
+/// rather than panicking if the base slice is too short. This is another name
+/// for `slice_as_array_prefix!` (or `slice_as_array_at!` given an offset) for
+/// code that would otherwise index the base slice before converting it.
+/// `checked_slice_as_array!(base_slice, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+/// `checked_slice_as_array!(base_slice, offset, [element_type; array_length]) -> Option<&[element_type; array_length]>`
+#[macro_export]
+macro_rules! checked_slice_as_array {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_prefix::<$t, { $len }>(s)
+    }};
+    ($slice:expr, $offset:expr, [$t:ty ; $len:expr] ) => {{
+        use $crate::reexport::SliceArgRef as _;
+        let s: &[$t] = $crate::reexport::slice_arg(($slice).slice_arg_ref());
+        $crate::slice_as_array_at::<$t, { $len }>(s, $offset)
+    }}
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn prefix_exact_length() {
+        let xs: [u8; 4] = [1, 2, 3, 4];
+        assert_eq!(slice_as_array_prefix!(&xs[..], [u8; 4]), Some(&[1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn prefix_longer() {
+        let packet: [u8; 6] = [0xca, 0xfe, 0xba, 0xbe, 0, 1];
+        let header: &[u8; 4] = slice_as_array_prefix!(&packet[..], [u8; 4]).unwrap();
+        assert_eq!(header, &[0xca, 0xfe, 0xba, 0xbe]);
+    }
+
+    #[test]
+    fn as_ref_arguments() {
+        let xs: Vec<u8> = vec![1, 2, 3, 4];
+        let mut ys = xs.clone();
+        assert_eq!(slice_as_array_prefix!(xs, [u8; 2]), Some(&[1, 2]));
+        assert_eq!(slice_as_array_suffix!(&xs, [u8; 2]), Some(&[3, 4]));
+        assert_eq!(strip_prefix_as_array!(xs, [1u8], [u8; 3]), Some(&[2, 3, 4]));
+        slice_as_array_prefix_mut!(&mut ys, [u8; 1]).unwrap()[0] = 0;
+        assert_eq!((xs.len(), ys[0]), (4, 0));
+    }
+
+    #[test]
+    fn prefix_shorter() {
+        let xs: [u8; 3] = [1, 2, 3];
+        assert_eq!(slice_as_array_prefix!(&xs[..], [u8; 4]), None);
+    }
+
+    #[test]
+    fn prefix_mut() {
+        let mut xs: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        slice_as_array_prefix_mut!(&mut xs[..], [u8; 2]).unwrap()[1] = 9;
+        assert_eq!(xs, [1, 9, 3, 4, 5, 6]);
+        assert_eq!(slice_as_array_prefix_mut!(&mut xs[..], [u8; 7]), None);
+    }
+
+    #[test]
+    fn suffix_exact_length() {
+        let xs: [u8; 4] = [1, 2, 3, 4];
+        assert_eq!(slice_as_array_suffix!(&xs[..], [u8; 4]), Some(&[1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn suffix_longer() {
+        let message: [u8; 6] = [0, 1, 0xca, 0xfe, 0xba, 0xbe];
+        let mac: &[u8; 4] = slice_as_array_suffix!(&message[..], [u8; 4]).unwrap();
+        assert_eq!(mac, &[0xca, 0xfe, 0xba, 0xbe]);
+    }
+
+    #[test]
+    fn suffix_shorter() {
+        let xs: [u8; 3] = [1, 2, 3];
+        assert_eq!(slice_as_array_suffix!(&xs[..], [u8; 4]), None);
+    }
+
+    #[test]
+    fn suffix_zero_length() {
+        let xs: [u8; 3] = [1, 2, 3];
+        assert_eq!(slice_as_array_suffix!(&xs[..], [u8; 0]), Some(&[]));
+        assert_eq!(slice_as_array_suffix!(&xs[..0], [u8; 0]), Some(&[]));
+    }
+
+    #[test]
+    fn suffix_mut() {
+        let mut xs: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        slice_as_array_suffix_mut!(&mut xs[..], [u8; 2]).unwrap()[0] = 9;
+        assert_eq!(xs, [1, 2, 3, 4, 9, 6]);
+        assert_eq!(slice_as_array_suffix_mut!(&mut xs[..], [u8; 7]), None);
+    }
+
+    #[test]
+    fn split_array() {
+        let packet: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        let (header, body) = slice_split_array!(&packet[..], [u8; 4]).unwrap();
+        assert_eq!(header, &[1, 2, 3, 4]);
+        assert_eq!(body, &[5, 6]);
+    }
+
+    #[test]
+    fn split_array_exact_length() {
+        let xs: [u8; 4] = [1, 2, 3, 4];
+        let (head, tail) = slice_split_array!(&xs[..], [u8; 4]).unwrap();
+        assert_eq!(head, &[1, 2, 3, 4]);
+        assert!(tail.is_empty());
+        assert_eq!(slice_split_array!(&xs[..], [u8; 5]), None);
+    }
+
+    #[test]
+    fn split_array_mut() {
+        let mut xs: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        {
+            let (head, tail) = slice_split_array_mut!(&mut xs[..], [u8; 2]).unwrap();
+            head[0] = tail[0];
+            tail[3] = head[1];
+        }
+        assert_eq!(xs, [3, 2, 3, 4, 5, 2]);
+        assert!(slice_split_array_mut!(&mut xs[..], [u8; 7]).is_none());
+    }
+
+    #[test]
+    fn rsplit_array() {
+        let message: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        let (body, trailer) = slice_rsplit_array!(&message[..], [u8; 4]).unwrap();
+        assert_eq!(body, &[1, 2]);
+        assert_eq!(trailer, &[3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn rsplit_array_exact_length() {
+        let xs: [u8; 4] = [1, 2, 3, 4];
+        let (head, tail) = slice_rsplit_array!(&xs[..], [u8; 4]).unwrap();
+        assert!(head.is_empty());
+        assert_eq!(tail, &[1, 2, 3, 4]);
+        assert_eq!(slice_rsplit_array!(&xs[..], [u8; 5]), None);
+    }
+
+    #[test]
+    fn rsplit_array_mut() {
+        let mut xs: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        {
+            let (head, tail) = slice_rsplit_array_mut!(&mut xs[..], [u8; 2]).unwrap();
+            head[0] = tail[1];
+            tail[0] = head[1];
+        }
+        assert_eq!(xs, [6, 2, 3, 4, 2, 6]);
+        assert!(slice_rsplit_array_mut!(&mut xs[..], [u8; 7]).is_none());
+    }
+
+    #[test]
+    fn at() {
+        let xs: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        assert_eq!(slice_as_array_at!(&xs[..], 2, [u8; 3]), Some(&[3, 4, 5]));
+        assert_eq!(slice_as_array_at!(&xs[..], 3, [u8; 3]), Some(&[4, 5, 6]));
+        assert_eq!(slice_as_array_at!(&xs[..], 4, [u8; 3]), None);
+        assert_eq!(slice_as_array_at!(&xs[..], 7, [u8; 0]), None);
+    }
+
+    #[test]
+    fn at_overflow() {
+        let xs: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        assert_eq!(slice_as_array_at!(&xs[..], usize::MAX, [u8; 3]), None);
+        assert_eq!(slice_as_array_at!(&xs[..], usize::MAX - 1, [u8; 2]), None);
+    }
+
+    #[test]
+    fn offsets_overflow() {
+        // Each `offset + N` overflows; none of them may wrap around to a small end.
+        let mut xs: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        assert_eq!(slice_as_array_at_mut!(&mut xs[..], usize::MAX, [u8; 2]), None);
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (usize::MAX, [u8; 2]), (0, [u8; 2])), None);
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (0, [u8; 2]), (usize::MAX - 1, [u8; 3])), None);
+        assert_eq!(slice_split_mid_array!(&xs[..], usize::MAX - 1, [u8; 2]), None);
+        assert!(slice_split_mid_array_mut!(&mut xs[..], usize::MAX, [u8; 1]).is_none());
+        assert_eq!(checked_slice_as_array!(&xs[..], usize::MAX, [u8; 2]), None);
+    }
+
+    #[test]
+    fn at_mut() {
+        let mut xs: [u8; 6] = [1, 2, 3, 4, 5, 6];
+        slice_as_array_at_mut!(&mut xs[..], 1, [u8; 2]).unwrap()[1] = 0;
+        assert_eq!(xs, [1, 2, 0, 4, 5, 6]);
+        assert_eq!(slice_as_array_at_mut!(&mut xs[..], usize::MAX, [u8; 2]), None);
+    }
+
+    #[test]
+    fn first_and_last() {
+        fn parse(buf: &[u8]) -> Option<(&[u8; 2], &[u8], &[u8; 1])> {
+            let (tag, rest) = (first_array!(buf, [u8; 2])?, &buf[2..]);
+            Some((tag, rest, last_array!(buf, [u8; 1])?))
+        }
+        let buf: [u8; 4] = [1, 2, 3, 4];
+        assert_eq!(parse(&buf), Some((&[1, 2], &[3, 4][..], &[4])));
+        assert_eq!(parse(&buf[..1]), None);
+    }
+
+    #[test]
+    fn first_and_last_mut() {
+        let mut xs: [u8; 4] = [1, 2, 3, 4];
+        first_array_mut!(&mut xs[..], [u8; 1]).unwrap()[0] = 0;
+        last_array_mut!(&mut xs[..], [u8; 1]).unwrap()[0] = 0;
+        assert_eq!(xs, [0, 2, 3, 0]);
+        assert_eq!(last_array_mut!(&mut xs[..], [u8; 5]), None);
+    }
+
+    #[test]
+    fn checked_does_not_panic() {
+        // `slice_as_array!(&xs[0..8], [u32; 8])` would panic in the indexing.
+        let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+        assert_eq!(checked_slice_as_array!(&xs, [u32; 8]), None);
+        assert_eq!(checked_slice_as_array!(&xs, [u32; 2]), Some(&[1, 2]));
+        assert_eq!(checked_slice_as_array!(&xs, 4, [u32; 2]), Some(&[16, 32]));
+        assert_eq!(checked_slice_as_array!(&xs, 5, [u32; 2]), None);
+    }
+
+    #[test]
+    fn two_arrays_mut() {
+        let mut xs: [u32; 6] = [1, 2, 3, 4, 5, 6];
+        {
+            let (a, b) = slice_two_arrays_mut!(&mut xs, (4, [u32; 2]), (0, [u32; 2])).unwrap();
+            ::std::mem::swap(a, b);
+        }
+        assert_eq!(xs, [5, 6, 3, 4, 1, 2]);
+        let (a, b) = slice_two_arrays_mut!(&mut xs, (0, [u32; 3]), (3, [u32; 3])).unwrap();
+        assert_eq!((a, b), (&mut [5, 6, 3], &mut [4, 1, 2]));
+    }
+
+    #[test]
+    fn two_arrays_mut_overlapping() {
+        let mut xs: [u32; 6] = [1, 2, 3, 4, 5, 6];
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (0, [u32; 3]), (2, [u32; 2])), None);
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (3, [u32; 2]), (1, [u32; 3])), None);
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (1, [u32; 2]), (1, [u32; 2])), None);
+    }
+
+    #[test]
+    fn two_arrays_mut_empty() {
+        let mut xs = [1u32, 2, 4, 8, 16];
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (2, [u32; 0]), (1, [u32; 3])), Some((&mut [], &mut [2, 4, 8])));
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (3, [u32; 2]), (4, [u32; 0])), Some((&mut [8, 16], &mut [])));
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (2, [u32; 0]), (2, [u32; 0])), Some((&mut [], &mut [])));
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (6, [u32; 0]), (1, [u32; 3])), None);
+    }
+
+    #[test]
+    fn two_arrays_mut_out_of_bounds() {
+        let mut xs: [u32; 6] = [1, 2, 3, 4, 5, 6];
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (0, [u32; 2]), (5, [u32; 2])), None);
+        assert_eq!(slice_two_arrays_mut!(&mut xs, (usize::MAX, [u32; 2]), (0, [u32; 2])), None);
+    }
+
+    #[test]
+    fn clone_split() {
+        let xs: Vec<String> = vec!["GET".into(), "/".into(), "HTTP/1.1".into()];
+        let (method, rest) = slice_clone_split!(&xs, [String; 1]).unwrap();
+        assert_eq!(method, ["GET"]);
+        assert_eq!(rest, &xs[1..]);
+        assert_eq!(slice_clone_split!(&xs, [String; 4]), None);
+    }
+
+    #[test]
+    fn split_mid_array() {
+        let record: [u8; 7] = [1, 2, 3, 4, 5, 6, 7];
+        let (before, array, after) = slice_split_mid_array!(&record[..], 2, [u8; 3]).unwrap();
+        assert_eq!((before, array, after), (&[1, 2][..], &[3, 4, 5], &[6, 7][..]));
+        let (before, _, after) = slice_split_mid_array!(&record[..], 4, [u8; 3]).unwrap();
+        assert_eq!((before.len(), after.len()), (4, 0));
+        assert_eq!(slice_split_mid_array!(&record[..], 5, [u8; 3]), None);
+        assert_eq!(slice_split_mid_array!(&record[..], usize::MAX, [u8; 3]), None);
+    }
+
+    #[test]
+    fn split_mid_array_mut() {
+        let mut xs: [u8; 5] = [1, 2, 3, 4, 5];
+        {
+            let (before, array, after) = slice_split_mid_array_mut!(&mut xs[..], 1, [u8; 2]).unwrap();
+            array[0] = before[0] + after[1];
+            before[0] = array[1];
+            after[0] = 0;
+        }
+        assert_eq!(xs, [3, 6, 3, 0, 5]);
+        assert!(slice_split_mid_array_mut!(&mut xs[..], 4, [u8; 2]).is_none());
+    }
+
+    #[test]
+    fn zip_arrays() {
+        let xs: [u8; 3] = [1, 2, 3];
+        let ys: [f32; 3] = [0.5, 1.5, 2.5];
+        let (a, b) = zip_slices_as_arrays!(&xs[..], &ys[..], [u8; 3], [f32; 3]).unwrap();
+        assert_eq!((a, b), (&[1, 2, 3], &[0.5, 1.5, 2.5]));
+        assert_eq!(zip_slices_as_arrays!(&xs[..], &ys[..2], [u8; 3], [f32; 3]), None);
+        assert_eq!(zip_slices_as_arrays!(&xs[..2], &ys[..], [u8; 3], [f32; 3]), None);
+    }
+
+    #[test]
+    fn zip_arrays_mut() {
+        let mut xs: [u32; 2] = [1, 2];
+        let mut ys: [u32; 2] = [10, 20];
+        {
+            let (a, b) = zip_slices_as_arrays_mut!(&mut xs[..], &mut ys[..], [u32; 2], [u32; 2]).unwrap();
+            for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+                *x += *y;
+                *y = 0;
+            }
+        }
+        assert_eq!((xs, ys), ([11, 22], [0, 0]));
+        assert!(zip_slices_as_arrays_mut!(&mut xs[..1], &mut ys[..], [u32; 2], [u32; 2]).is_none());
+    }
+
+    #[test]
+    fn strip_prefix() {
+        let frame: &[u8] = b"SAA1\x01\x02\x03";
+        assert_eq!(strip_prefix_as_array!(frame, b"SAA1", [u8; 3]), Some(&[1, 2, 3]));
+        assert_eq!(strip_prefix_as_array!(frame, b"SAA2", [u8; 3]), None);
+        assert_eq!(strip_prefix_as_array!(frame, b"SAA1", [u8; 2]), None);
+        assert_eq!(strip_prefix_as_array!(frame, &[], [u8; 7]), Some(b"SAA1\x01\x02\x03"));
+    }
+
+    #[test]
+    fn at_saturating() {
+        let xs = [0u8, 1, 2, 3, 4, 5];
+        assert_eq!(slice_as_array_at_saturating!(&xs, 1, [u8; 3]), Some(&[1, 2, 3]));
+        assert_eq!(slice_as_array_at_saturating!(&xs, 3, [u8; 3]), Some(&[3, 4, 5]));
+        assert_eq!(slice_as_array_at_saturating!(&xs, 4, [u8; 3]), Some(&[3, 4, 5]));
+        assert_eq!(slice_as_array_at_saturating!(&xs, usize::MAX, [u8; 3]), Some(&[3, 4, 5]));
+        assert_eq!(slice_as_array_at_saturating!(&xs, 9, [u8; 6]), Some(&xs));
+        assert!(slice_as_array_at_saturating!(&xs, 0, [u8; 7]).is_none());
+        assert_eq!(slice_as_array_at_saturating!(&xs[..0], 3, [u8; 0]), Some(&[]));
+    }
+
+    #[test]
+    fn at_saturating_mut() {
+        let mut xs = [0u8, 1, 2, 3];
+        slice_as_array_at_saturating_mut!(&mut xs, 3, [u8; 2]).unwrap()[0] = 9;
+        assert_eq!(xs, [0, 1, 9, 3]);
+        assert!(slice_as_array_at_saturating_mut!(&mut xs, 0, [u8; 5]).is_none());
+    }
+}
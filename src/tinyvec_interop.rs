@@ -0,0 +1,93 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions from `tinyvec` vectors, behind the `tinyvec` feature. These
+//! stay in safe code, as users of `tinyvec` would expect.
+
+#![forbid(unsafe_code)]
+
+#[cfg(all(feature="alloc", feature="use_std"))] use std::convert::TryFrom;
+#[cfg(all(feature="alloc", not(feature="use_std")))] use core::convert::TryFrom;
+use tinyvec::ArrayVec;
+#[cfg(feature="alloc")]
+use tinyvec::TinyVec;
+use {check, report_length_mismatch};
+
+/// Move the elements of a full `tinyvec::ArrayVec` into an array.
+/// Returns the `ArrayVec` unchanged if it holds fewer than `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn tinyvec_arrayvec_into_array<T: Default, const N: usize>(vec: ArrayVec<[T; N]>) -> Result<[T; N], ArrayVec<[T; N]>> {
+    if !check::has_len(&vec, N) {
+        report_length_mismatch::<T>(N, vec.len());
+        return Err(vec);
+    }
+    Ok(vec.into_inner())
+}
+
+/// Move the elements of a `TinyVec` into an array, whether they are stored
+/// inline or on the heap.
+/// Returns the `TinyVec` unchanged if it does not have exactly `N` elements.
+#[cfg(feature="alloc")]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn tinyvec_into_array<T: Default, const N: usize>(vec: TinyVec<[T; N]>) -> Result<[T; N], TinyVec<[T; N]>> {
+    match vec {
+        TinyVec::Inline(inline) => tinyvec_arrayvec_into_array(inline).map_err(TinyVec::Inline),
+        TinyVec::Heap(heap) => match <[T; N]>::try_from(heap) {
+            Ok(array) => Ok(array),
+            Err(heap) => {
+                report_length_mismatch::<T>(N, heap.len());
+                Err(TinyVec::Heap(heap))
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tinyvec::ArrayVec;
+    #[cfg(feature="alloc")]
+    use tinyvec::TinyVec;
+    use super::tinyvec_arrayvec_into_array;
+    #[cfg(feature="alloc")]
+    use super::tinyvec_into_array;
+
+    #[test]
+    fn arrayvec_into_array() {
+        let mut xs: ArrayVec<[u32; 3]> = ArrayVec::new();
+        xs.extend_from_slice(&[1, 2]);
+        let mut xs = tinyvec_arrayvec_into_array(xs).unwrap_err();
+        xs.push(3);
+        assert_eq!(tinyvec_arrayvec_into_array(xs), Ok([1, 2, 3]));
+    }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn into_array() {
+        let inline: TinyVec<[u32; 2]> = TinyVec::from(&[1, 2][..]);
+        assert!(inline.is_inline());
+        assert_eq!(tinyvec_into_array(inline), Ok([1, 2]));
+
+        // A spilled vector may shrink back to `N` elements while staying on the heap.
+        let mut heap: TinyVec<[u32; 2]> = TinyVec::from(&[1, 2, 3][..]);
+        heap.pop();
+        assert!(heap.is_heap());
+        assert_eq!(tinyvec_into_array(heap), Ok([1, 2]));
+    }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn into_array_wrong_length() {
+        let xs: TinyVec<[u32; 2]> = TinyVec::from(&[1, 2, 3][..]);
+        let xs = tinyvec_into_array(xs).unwrap_err();
+        assert_eq!(&xs[..], &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Casts between arrays of types with the same layout, such as a
+//! `#[repr(transparent)]` newtype and the type it wraps.
+
+#[cfg(feature="use_std")] use std::mem;
+#[cfg(not(feature="use_std"))] use core::mem;
+use reexport;
+
+/// Promises that `Self` and `U` have the same size, alignment, and validity,
+/// so that any `Self` can be reinterpreted as a `U`.
+///
+/// Implement it for a `#[repr(transparent)]` newtype and the type it wraps,
+/// in either or both directions:
+///
+/// ```ignore
+/// #[repr(transparent)]
+/// struct Id(u32);
+///
+/// unsafe impl SameLayout<u32> for Id {}
+/// unsafe impl SameLayout<Id> for u32 {}
+/// ```
+///
+/// # Safety
+///
+/// Every value of `Self` must be a valid value of `U` with the same meaning
+/// to unsafe code. Being `repr(transparent)` over `U` is enough. Only the size
+/// and alignment are checked by the compiler.
+pub unsafe trait SameLayout<U> {}
+
+unsafe impl<T> SameLayout<T> for T {}
+
+struct AssertSameLayout<T, U>(T, U);
+
+impl<T, U> AssertSameLayout<T, U> {
+    // Evaluated, and so checked, when the function using it is monomorphized.
+    const HOLDS: () = assert!(mem::size_of::<T>() == mem::size_of::<U>() && mem::align_of::<T>() == mem::align_of::<U>(),
+        "element types do not have the same layout");
+}
+
+/// Reinterpret an array of `T` as an array of `U`, without copying.
+/// A mismatch in size or alignment is a compile error.
+#[inline]
+pub fn transmute_array_ref<T: SameLayout<U>, U, const N: usize>(array: &[T; N]) -> &[U; N] {
+    let () = AssertSameLayout::<T, U>::HOLDS;
+    let ptr = array as *const [T; N] as *const [U; N];
+    reexport::debug_assert_aligned(ptr);
+    unsafe { &*ptr }
+}
+
+/// Reinterpret a mutable array of `T` as a mutable array of `U`, without copying.
+/// Since a `U` written through the result is read back as a `T`, the layouts
+/// must be promised to match in both directions.
+#[inline]
+pub fn transmute_array_mut<T: SameLayout<U>, U: SameLayout<T>, const N: usize>(array: &mut [T; N]) -> &mut [U; N] {
+    let () = AssertSameLayout::<T, U>::HOLDS;
+    let ptr = array as *mut [T; N] as *mut [U; N];
+    reexport::debug_assert_aligned(ptr);
+    unsafe { &mut *ptr }
+}
+
+/// Reinterpret an array reference as one of another element type with the same layout.
+/// `transmute_array_ref!(array_ref, [from_type; array_length], [to_type; array_length]) -> &[to_type; array_length]`
+#[macro_export]
+macro_rules! transmute_array_ref {
+    ($array:expr, [$t:ty ; $n:expr], [$u:ty ; $m:expr] ) => {{
+        let a: &[$t; $n] = $array;
+        let array: &[$u; $m] = $crate::transmute_array_ref(a);
+        array
+    }}
+}
+
+/// Reinterpret a mutable array reference as one of another element type with the same layout.
+/// `transmute_array_mut!(array_ref, [from_type; array_length], [to_type; array_length]) -> &mut [to_type; array_length]`
+#[macro_export]
+macro_rules! transmute_array_mut {
+    ($array:expr, [$t:ty ; $n:expr], [$u:ty ; $m:expr] ) => {{
+        let a: &mut [$t; $n] = $array;
+        let array: &mut [$u; $m] = $crate::transmute_array_mut(a);
+        array
+    }}
+}
+
+#[cfg(test)]
+mod test {
+    use super::SameLayout;
+
+    #[repr(transparent)]
+    #[derive(Debug, PartialEq)]
+    struct Id(u32);
+
+    unsafe impl SameLayout<u32> for Id {}
+    unsafe impl SameLayout<Id> for u32 {}
+
+    #[test]
+    fn newtype_ref() {
+        let raw: [u32; 3] = [7, 8, 9];
+        let ids = transmute_array_ref!(&raw, [u32; 3], [Id; 3]);
+        assert_eq!(ids, &[Id(7), Id(8), Id(9)]);
+        let back = transmute_array_ref!(ids, [Id; 3], [u32; 3]);
+        assert_eq!(back.as_ptr(), raw.as_ptr());
+        assert_eq!(transmute_array_ref!(&raw, [u32; 3], [u32; 3]), &raw);
+    }
+
+    #[test]
+    fn newtype_mut() {
+        let mut ids = [Id(1), Id(2)];
+        transmute_array_mut!(&mut ids, [Id; 2], [u32; 2])[1] = 5;
+        assert_eq!(ids, [Id(1), Id(5)]);
+    }
+}
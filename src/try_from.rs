@@ -0,0 +1,71 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `TryFrom`-style conversions that all report a `SliceLengthError`.
+//!
+//! Unlike the standard `TryFrom` impls for arrays, these share one error type
+//! between the borrowing and the cloning cases.
+
+use {slice_as_array_mut, slice_to_array_clone_checked, try_slice_as_array, SliceLengthError};
+
+/// Convert a slice to an array, like `<&[T; N]>::try_from(slice)`.
+/// This is the same as `try_slice_as_array`.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn try_from_slice<T, const N: usize>(slice: &[T]) -> Result<&[T; N], SliceLengthError> {
+    try_slice_as_array(slice)
+}
+
+/// Convert a mutable slice to a mutable array, like `<&mut [T; N]>::try_from(slice)`.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn try_from_slice_mut<T, const N: usize>(slice: &mut [T]) -> Result<&mut [T; N], SliceLengthError> {
+    let actual = slice.len();
+    slice_as_array_mut(slice).ok_or(SliceLengthError { expected: N, actual })
+}
+
+/// Convert a slice to an array by cloning each element, like `<[T; N]>::try_from(slice)`
+/// but without requiring `T: Copy`. This is the same as `slice_to_array_clone_checked`.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn try_from_slice_cloned<T: Clone, const N: usize>(slice: &[T]) -> Result<[T; N], SliceLengthError> {
+    slice_to_array_clone_checked(slice)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{try_from_slice, try_from_slice_mut, try_from_slice_cloned};
+    use SliceLengthError;
+
+    #[test]
+    fn borrowed() {
+        let xs: [u8; 3] = [1, 2, 3];
+        assert_eq!(try_from_slice::<u8, 3>(&xs[..]), Ok(&[1, 2, 3]));
+        assert_eq!(try_from_slice::<u8, 2>(&xs[..]), Err(SliceLengthError { expected: 2, actual: 3 }));
+    }
+
+    #[test]
+    fn borrowed_mut() {
+        let mut xs: [u8; 3] = [1, 2, 3];
+        try_from_slice_mut::<u8, 2>(&mut xs[1..]).unwrap()[0] = 0;
+        assert_eq!(xs, [1, 0, 3]);
+        assert_eq!(try_from_slice_mut::<u8, 2>(&mut xs[..]), Err(SliceLengthError { expected: 2, actual: 3 }));
+    }
+
+    #[test]
+    fn cloned() {
+        let xs: [String; 2] = ["a".to_string(), "b".to_string()];
+        let ys: [String; 2] = try_from_slice_cloned(&xs[..]).unwrap();
+        assert_eq!(ys, xs);
+        assert_eq!(try_from_slice_cloned::<String, 3>(&xs[..]), Err(SliceLengthError { expected: 3, actual: 2 }));
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions between arrays and tuples whose elements all have one type.
+//!
+//! Both directions move the elements, so they work for any element type,
+//! `Copy` or not; a `Copy` array is simply copied into the call. They are
+//! implemented for 1 to 12 elements, the same arities for which the standard
+//! library implements traits on tuples.
+
+/// Arrays that can be moved into a tuple of the same length.
+pub trait ArrayTuple {
+    /// The tuple with one field per element of the array.
+    type Tuple;
+    /// Move the elements of the array into a tuple, in order.
+    fn into_tuple(self) -> Self::Tuple;
+}
+
+/// Tuples whose fields all have one type, which can be moved into an array.
+pub trait TupleArray {
+    /// The array with one element per field of the tuple.
+    type Array;
+    /// Move the fields of the tuple into an array, in order.
+    fn into_array(self) -> Self::Array;
+}
+
+macro_rules! element {
+    ($name:ident) => { T }
+}
+
+macro_rules! impl_tuple {
+    ($($n:literal: ($($name:ident)+))*) => {
+        $(
+            impl<T> ArrayTuple for [T; $n] {
+                type Tuple = ($(element!($name),)+);
+                #[inline]
+                fn into_tuple(self) -> Self::Tuple {
+                    let [$($name),+] = self;
+                    ($($name,)+)
+                }
+            }
+
+            impl<T> TupleArray for ($(element!($name),)+) {
+                type Array = [T; $n];
+                #[inline]
+                fn into_array(self) -> Self::Array {
+                    let ($($name,)+) = self;
+                    [$($name),+]
+                }
+            }
+        )*
+    }
+}
+
+impl_tuple! {
+    1: (a)
+    2: (a b)
+    3: (a b c)
+    4: (a b c d)
+    5: (a b c d e)
+    6: (a b c d e f)
+    7: (a b c d e f g)
+    8: (a b c d e f g h)
+    9: (a b c d e f g h i)
+    10: (a b c d e f g h i j)
+    11: (a b c d e f g h i j k)
+    12: (a b c d e f g h i j k l)
+}
+
+/// Move the elements of an array of 1 to 12 elements into a tuple.
+#[inline]
+pub fn array_as_tuple<A: ArrayTuple>(array: A) -> A::Tuple {
+    array.into_tuple()
+}
+
+/// Move the fields of a tuple of 1 to 12 fields of one type into an array.
+#[inline]
+pub fn tuple_as_array<U: TupleArray>(tuple: U) -> U::Array {
+    tuple.into_array()
+}
+
+/// Move the elements of an array of 1 to 12 elements into a tuple.
+/// `array_as_tuple!(array)`
+#[macro_export]
+macro_rules! array_as_tuple {
+    ($array:expr) => {
+        $crate::array_as_tuple($array)
+    }
+}
+
+/// Move the fields of a tuple of 1 to 12 fields of one type into an array.
+/// `tuple_as_array!(tuple)`
+#[macro_export]
+macro_rules! tuple_as_array {
+    ($tuple:expr) => {
+        $crate::tuple_as_array($tuple)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{array_as_tuple, tuple_as_array, ArrayTuple, TupleArray};
+
+    #[test]
+    fn copy_round_trip() {
+        let xs = [1u8, 2, 3];
+        let (a, b, c) = array_as_tuple!(xs);
+        assert_eq!((a, b, c), (1, 2, 3));
+        assert_eq!(tuple_as_array!((a, b, c)), xs);
+        assert_eq!(array_as_tuple([7u32]), (7,));
+        assert_eq!(tuple_as_array((7u32,)), [7]);
+    }
+
+    #[test]
+    fn by_move() {
+        let names = [String::from("a"), String::from("b")];
+        let (first, second) = array_as_tuple(names);
+        assert_eq!((first.as_str(), second.as_str()), ("a", "b"));
+        let back: [String; 2] = (second, first).into_array();
+        assert_eq!(back, ["b", "a"]);
+    }
+
+    #[test]
+    fn largest_arity() {
+        let xs: [u16; 12] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+        let tuple = xs.into_tuple();
+        assert_eq!(tuple.11, 11);
+        assert_eq!(tuple_as_array(tuple), xs);
+    }
+}
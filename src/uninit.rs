@@ -0,0 +1,85 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fixed-size views of uninitialized buffers.
+
+#[cfg(feature="use_std")] use std::mem::{ManuallyDrop, MaybeUninit};
+#[cfg(not(feature="use_std"))] use core::mem::{ManuallyDrop, MaybeUninit};
+use {reexport, slice_as_array_mut};
+
+/// Convert a mutable slice of uninitialized elements to a mutable array of them.
+/// Returns `None` if the slice does not have exactly `N` elements.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn uninit_slice_as_array_mut<T, const N: usize>(slice: &mut [MaybeUninit<T>]) -> Option<&mut [MaybeUninit<T>; N]> {
+    slice_as_array_mut(slice)
+}
+
+/// Take the values out of an array of `MaybeUninit`s once all are initialized.
+///
+/// # Safety
+///
+/// Every element of `array` must be initialized, as for `MaybeUninit::assume_init`.
+#[inline]
+pub unsafe fn assume_init_array<T, const N: usize>(array: [MaybeUninit<T>; N]) -> [T; N] {
+    // `MaybeUninit<T>` has the layout of `T`, and so the arrays of each match.
+    let array = ManuallyDrop::new(array);
+    reexport::ptr_read(&*array as *const [MaybeUninit<T>; N] as *const [T; N])
+}
+
+/// View an array of `MaybeUninit`s that are all initialized as an array of values.
+///
+/// # Safety
+///
+/// Every element of `array` must be initialized, as for `MaybeUninit::assume_init_mut`.
+#[inline]
+pub unsafe fn assume_init_array_mut<T, const N: usize>(array: &mut [MaybeUninit<T>; N]) -> &mut [T; N] {
+    &mut *(array as *mut [MaybeUninit<T>; N] as *mut [T; N])
+}
+
+/// Convert a mutable slice of uninitialized elements to a mutable array of them.
+/// `uninit_slice_as_array_mut!(mutable_slice, [element_type; array_length]) -> Option<&mut [MaybeUninit<element_type>; array_length]>`
+#[macro_export]
+macro_rules! uninit_slice_as_array_mut {
+    ($slice:expr, [$t:ty ; $len:expr] ) => {{
+        let s: &mut [$crate::reexport::MaybeUninit<$t>] = $crate::reexport::as_mut_slice_arg($slice);
+        $crate::uninit_slice_as_array_mut::<$t, { $len }>(s)
+    }}
+}
+
+#[cfg(test)]
+mod test {
+    use std::mem::MaybeUninit;
+    use super::{assume_init_array, assume_init_array_mut};
+
+    #[test]
+    fn fill_uninit() {
+        let mut scratch: [MaybeUninit<String>; 5] = [const { MaybeUninit::uninit() }; 5];
+        let record = uninit_slice_as_array_mut!(&mut scratch[1..3], [String; 2]).unwrap();
+        for (i, slot) in record.iter_mut().enumerate() {
+            slot.write(i.to_string());
+        }
+        let values = unsafe { assume_init_array_mut(record) };
+        values[1].push('!');
+        assert_eq!(values, &["0", "1!"]);
+        // `MaybeUninit` never drops its contents, so drop the strings here.
+        unsafe { ::std::ptr::drop_in_place(values) };
+        assert!(uninit_slice_as_array_mut!(&mut scratch[..], [String; 4]).is_none());
+    }
+
+    #[test]
+    fn by_value() {
+        let mut buffer = [MaybeUninit::<Vec<u8>>::uninit(), MaybeUninit::uninit()];
+        buffer[0].write(vec![1]);
+        buffer[1].write(vec![2, 3]);
+        let values = unsafe { assume_init_array(buffer) };
+        assert_eq!(values, [vec![1], vec![2, 3]]);
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reinterpretation of bytes checked by `zerocopy`, behind the `zerocopy` feature.
+
+#[cfg(feature="use_std")] use std::mem;
+#[cfg(not(feature="use_std"))] use core::mem;
+use zerocopy::{FromBytes, Immutable};
+use {report_length_mismatch, report_mismatch, Mismatch};
+
+/// Reinterpret a byte slice as an array of `N` values of type `U`.
+/// Returns `None` if the slice is not exactly `N * size_of::<U>()` bytes
+/// long, or if it is not aligned for `U`.
+///
+/// Unlike `bytes_as_array`, this accepts any type that `zerocopy` can show
+/// to be valid for every bit pattern, and needs no unsafe code.
+#[inline]
+#[track_caller]
+#[must_use = "converting to an array produces a value that should be used"]
+pub fn from_bytes_array<U: FromBytes + Immutable, const N: usize>(bytes: &[u8]) -> Option<&[U; N]> {
+    if Some(bytes.len()) != N.checked_mul(mem::size_of::<U>()) {
+        report_length_mismatch::<u8>(N.saturating_mul(mem::size_of::<U>()), bytes.len());
+        return None;
+    }
+    let array = <[U; N]>::ref_from_bytes(bytes).ok();
+    if array.is_none() {
+        // With the length right, only the alignment can be wrong.
+        let align = mem::align_of::<U>();
+        report_mismatch::<U>(N, Mismatch::Misaligned { align, offset: bytes.as_ptr() as usize % align });
+    }
+    array
+}
+
+#[cfg(test)]
+mod test {
+    use zerocopy::IntoBytes;
+    use super::from_bytes_array;
+
+    #[test]
+    fn from_bytes() {
+        let words: [u16; 4] = [1, 2, 3, 4];
+        let bytes = words.as_bytes();
+        assert_eq!(from_bytes_array::<u16, 4>(bytes), Some(&words));
+        let pairs: &[[u16; 2]; 2] = from_bytes_array(bytes).unwrap();
+        assert_eq!(pairs, &[[1, 2], [3, 4]]);
+    }
+
+    #[test]
+    fn from_bytes_wrong_length_or_alignment() {
+        let words: [u16; 4] = [1, 2, 3, 4];
+        let bytes = words.as_bytes();
+        assert_eq!(from_bytes_array::<u16, 3>(bytes), None);
+        assert_eq!(from_bytes_array::<u16, 2>(&bytes[1..5]), None);
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks that `slice_to_array_copy` copies the whole array with one `memcpy`
+// and no per-element loop, by compiling `tests/codegen/copy.rs` to LLVM IR
+// with optimizations and reading the function's body.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+// The rlibs of this crate in `target/<profile>/deps`, next to the test
+// binary, newest first. Older ones may be from other features or compilers.
+fn crate_rlibs(deps: &Path) -> Vec<PathBuf> {
+    let mut rlibs: Vec<PathBuf> = fs::read_dir(deps).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.starts_with("libslice_as_array-") && name.ends_with(".rlib")
+        })
+        .collect();
+    rlibs.sort_by_key(|path| fs::metadata(path).unwrap().modified().unwrap());
+    rlibs.reverse();
+    rlibs
+}
+
+// Whether any branch jumps back to its own block or an earlier one, as a
+// loop over the elements would. LLVM prints blocks in order, so a forward
+// branch always names a later label.
+fn has_back_edge(body: &str) -> bool {
+    let mut seen = vec!["start"];
+    for line in body.lines().skip(1) {
+        if !line.starts_with(' ') {
+            if let Some(colon) = line.find(':') {
+                seen.push(&line[..colon]);
+            }
+        } else if line.trim_start().starts_with("br ") {
+            let mut targets = line.split("label %").skip(1).map(|target| target.split(',').next().unwrap().trim());
+            if targets.any(|target| seen.contains(&target)) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+// Ignored by default, as it runs `rustc` itself and depends on its output:
+// run it with `cargo test --test codegen -- --ignored`.
+#[test]
+#[ignore]
+fn copy_is_one_memcpy() {
+    let deps = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let out_dir = env::temp_dir().join(format!("slice_as_array-codegen-{}", process::id()));
+    fs::create_dir_all(&out_dir).unwrap();
+    let ir = out_dir.join("copy.ll");
+
+    // Each rlib is tried in turn, and the errors of those that failed are
+    // kept in case none of them compiles.
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let mut errors = String::new();
+    let compiled = crate_rlibs(&deps).iter().any(|rlib| {
+        let output = Command::new(&rustc)
+            .args(["--crate-type", "lib", "--emit", "llvm-ir", "-C", "opt-level=3", "-C", "debug-assertions=off"])
+            .arg("-L").arg(&deps)
+            .arg("--extern").arg(format!("slice_as_array={}", rlib.display()))
+            .arg("-o").arg(&ir)
+            .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/codegen/copy.rs"))
+            .output()
+            .unwrap();
+        if !output.status.success() {
+            errors.push_str(&format!("{}:\n{}\n", rlib.display(), String::from_utf8_lossy(&output.stderr)));
+        }
+        output.status.success()
+    });
+    assert!(compiled, "tests/codegen/copy.rs did not compile against any slice_as_array rlib\n{}", errors);
+
+    let ir = fs::read_to_string(&ir).unwrap();
+    let start = ir.find("@copy_to_array(").expect("copy_to_array was not emitted");
+    let body = &ir[start..start + ir[start..].find("\n}\n").unwrap()];
+    assert_eq!(body.matches("@llvm.memcpy").count(), 1, "{}", body);
+    assert!(!has_back_edge(body), "{}", body);
+    fs::remove_dir_all(&out_dir).unwrap();
+}
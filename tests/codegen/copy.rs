@@ -0,0 +1,17 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Compiled to LLVM IR by `tests/codegen.rs`.
+
+extern crate slice_as_array;
+
+#[no_mangle]
+pub fn copy_to_array(slice: &[u64]) -> Option<[u64; 256]> {
+    slice_as_array::slice_to_array_copy(slice)
+}
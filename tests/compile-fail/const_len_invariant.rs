@@ -0,0 +1,17 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use] extern crate slice_as_array;
+
+const BLOCK: usize = 6;
+
+fn main() {
+    let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+    let block = slice_as_array_const_len!(&xs, BLOCK, BLOCK % 4 == 0); //~error: array length invariant does not hold: BLOCK % 4 == 0
+}
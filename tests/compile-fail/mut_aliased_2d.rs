@@ -0,0 +1,18 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use] extern crate slice_as_array;
+
+fn main() {
+    let mut xs = [0u32; 12];
+    let rows = slice_as_2d_array_mut!(&mut xs[..], [[u32; 4]; 3]).unwrap();
+
+    xs[0] = 5; //~error: cannot assign to `xs[_]` because it is borrowed
+    rows[1][0] = 6;
+}
@@ -0,0 +1,18 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use] extern crate slice_as_array;
+
+fn main() {
+    let mut xs: Vec<u8> = vec![1, 2, 3];
+    let array = slice_as_array_mut!(&mut xs, [u8; 3]).unwrap();
+    // Pushing could reallocate and leave `array` dangling.
+    xs.push(4); //~error: cannot borrow `xs` as mutable more than once at a time
+    array[0] = 5;
+}
@@ -0,0 +1,15 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use] extern crate slice_as_array;
+
+fn main() {
+    // Anything that is `AsRef<[T]>` is accepted, but a plain value is not.
+    let byte_array = slice_as_array!(5u8, [u8; 1]); //~error: `slice_as_array!` expects a slice or an `AsRef<[T]>` value, found `u8`
+}
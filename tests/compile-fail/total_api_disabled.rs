@@ -0,0 +1,16 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use] extern crate slice_as_array;
+
+fn main() {
+    // Without the `total_api` feature only `slice_as_array!` takes a range.
+    let xs = [0u32; 10];
+    let array = slice_to_array_copy!(&xs, 0..4, [u32; 4]); //~error: needs the `total_api` feature
+}
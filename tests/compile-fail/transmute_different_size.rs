@@ -0,0 +1,22 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use] extern crate slice_as_array;
+
+use slice_as_array::SameLayout;
+
+struct Wide(u64);
+
+// A wrong promise still cannot make the reference cast read out of bounds.
+unsafe impl SameLayout<Wide> for u32 {}
+
+fn main() {
+    let raw = [0u32; 2];
+    let wide = transmute_array_ref!(&raw, [u32; 2], [Wide; 2]); //~error: element types do not have the same layout
+}
@@ -0,0 +1,16 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[macro_use] extern crate slice_as_array;
+
+fn main() {
+    let xs = [0u8; 4];
+    let ys = [0u16; 4];
+    let arrays = zip_slices_as_arrays!(&xs[..], &ys[..], [u8; 4], [u16; 3]); //~error: mismatched types
+}
@@ -0,0 +1,71 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Run with `cargo test --features log`. Every failed conversion should be
+// reported at the line in this file that asked for it, not inside the crate.
+#![cfg(all(feature = "log", feature = "use_std"))]
+
+#[macro_use] extern crate slice_as_array;
+extern crate log;
+
+use std::sync::Mutex;
+
+static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Capture;
+
+impl log::Log for Capture {
+    fn enabled(&self, _: &log::Metadata) -> bool { true }
+    fn log(&self, record: &log::Record) { MESSAGES.lock().unwrap().push(record.args().to_string()) }
+    fn flush(&self) {}
+}
+
+fn reported_at(line: u32, expected: &str) -> bool {
+    MESSAGES.lock().unwrap().iter().any(|message| {
+        message.starts_with(&format!("array conversion at tests/log.rs:{}:", line)) && message.ends_with(expected)
+    })
+}
+
+// The logger is global, so everything is checked from a single test.
+#[test]
+fn reports_caller_location() {
+    log::set_logger(&Capture).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    let mut xs = [1u8, 2, 3, 4, 5];
+    let mut vec = xs.to_vec();
+    // Four bytes starting one past a multiple of 4, so never aligned for a `u32`.
+    let buffer = [0u8; 8];
+    let misaligned = 1 + (4 - buffer.as_ptr() as usize % 4) % 4;
+    let checks = [
+        (line!(), slice_as_array::slice_as_array_prefix::<u8, 6>(&xs).is_none(), "expected a length of at least 6 for [u8; 6], found length 5"),
+        (line!(), slice_as_array::slice_as_array_suffix_mut::<u8, 6>(&mut xs).is_none(), "expected a length of at least 6 for [u8; 6], found length 5"),
+        (line!(), slice_as_array::slice_split_array::<u8, 7>(&xs).is_none(), "expected a length of at least 7 for [u8; 7], found length 5"),
+        (line!(), slice_as_array::slice_as_array_at::<u8, 3>(&xs, 4).is_none(), "expected [u8; 3] at 4..7, found a slice of length 5"),
+        (line!(), slice_as_array::slice_as_array_range::<u8, 3>(&xs, 3..6).is_none(), "expected [u8; 3] at 3..6, found a slice of length 5"),
+        (line!(), slice_as_array::slice_two_arrays_mut::<u8, 2, 2>(&mut xs, 0, 1).is_none(), "expected [u8; 2] at offset 0 apart from [u8; 2] at offset 1, found them overlapping"),
+        (line!(), slice_as_array::strip_prefix_as_array::<u8, 4>(&xs, &[9]).is_none(), "expected [u8; 4] after a prefix of length 1, found a slice not starting with it"),
+        (line!(), slice_as_array::zip_slices_as_arrays::<u8, u8, 3>(&xs[..3], &xs[..2]).is_none(), "expected [u8; 3], found length 2"),
+        (line!(), slice_as_array::slice_as_2d_array::<u8, 2, 2>(&xs).is_none(), "expected [u8; 4], found length 5"),
+        (line!(), slice_as_array::row_as_array::<u8, 2>(&xs, 2, usize::MAX).is_none(), "overflowing usize"),
+        (line!(), slice_as_array::exact_array_chunks::<u8, 2>(&xs).is_none(), "expected a length that is a multiple of 2 for [u8; 2], found length 5"),
+        (line!(), slice_as_array::bytes_as_array::<u8, 4>(&xs).is_none(), "expected [u8; 4], found length 5"),
+        (line!(), slice_as_array::bytes_as_array::<u32, 1>(&buffer[misaligned..misaligned + 4]).is_none(), "expected [u32; 1] aligned to 4 bytes, found an address misaligned by 1"),
+        (line!(), slice_as_array::array_from_iter::<u8, _, 6>(xs.iter().cloned()).is_none(), "expected [u8; 6], found length 5"),
+        (line!(), slice_as_array::array_from_iter_exact::<u8, _, 3>(xs.iter().cloned()).is_none(), "expected [u8; 3], found a length of more than 3"),
+        (line!(), slice_as_array::slice_to_array_default_fallible::<u8, 3>(&xs).is_none(), "expected a length of at most 3 for [u8; 3], found length 5"),
+        (line!(), slice_as_array::vec_split_off_array::<u8, 6>(&mut vec).is_none(), "expected a length of at least 6 for [u8; 6], found length 5"),
+        (line!(), slice_as_array::vec_into_array::<u8, 3>(xs.to_vec()).is_err(), "expected [u8; 3], found length 5"),
+        (line!(), slice_to_array_clone!(&xs, [u8; 2]).is_none(), "expected [u8; 2], found length 5"),
+    ];
+    for &(line, failed, expected) in &checks {
+        assert!(failed, "conversion at line {} should fail", line);
+        assert!(reported_at(line, expected), "conversion at line {} was not reported here", line);
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Run with `cargo test --no-default-features`.
+#![cfg(not(feature = "use_std"))]
+#![no_std]
+
+#[macro_use] extern crate slice_as_array;
+#[cfg(feature = "heapless")] extern crate heapless;
+
+#[test]
+fn as_array() {
+    let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+    let xs_middle: &[u32; 3] = slice_as_array!(&xs[1..4], [u32; 3]).unwrap();
+    assert_eq!(xs_middle, &[2, 4, 8]);
+}
+
+#[test]
+fn as_array_mut() {
+    let mut xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+    {
+        let xs_middle: &mut [u32; 3] = slice_as_array_mut!(&mut xs[1..4], [u32; 3]).unwrap();
+        xs_middle[0] = 3;
+    }
+    assert_eq!(xs, [1, 3, 4, 8, 16, 32]);
+}
+
+#[test]
+fn to_array_clone() {
+    let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+    let xs_middle: [u32; 3] = slice_to_array_clone!(&xs[1..4], [u32; 3]).unwrap();
+    assert_eq!(xs_middle, [2, 4, 8]);
+}
+
+#[test]
+fn to_array_copy() {
+    let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+    let xs_middle: [u32; 3] = slice_to_array_copy!(&xs[1..4], [u32; 3]).unwrap();
+    assert_eq!(xs_middle, [2, 4, 8]);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn heapless_into_array() {
+    let mut frame: heapless::Vec<u8, 16> = heapless::Vec::new();
+    frame.extend_from_slice(&[0xca, 0xfe]).unwrap();
+    assert_eq!(slice_as_array::heapless_into_array(frame), Ok([0xca, 0xfe]));
+}
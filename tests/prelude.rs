@@ -0,0 +1,33 @@
+// Copyright 2016 Peter Reid. See the COPYRIGHT file at the top-level
+// directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The prelude alone, without `#[macro_use]`, is enough for every API style.
+extern crate slice_as_array;
+
+use slice_as_array::prelude::*;
+
+#[test]
+fn functions() {
+    let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+    assert_eq!(slice_as_array::<u32, 3>(&xs[..3]), Some(&[1, 2, 4]));
+    assert_eq!(try_slice_as_array::<u32, 3>(&xs), Err(SliceLengthError { expected: 3, actual: 6 }));
+}
+
+#[test]
+fn macros() {
+    let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+    assert_eq!(slice_as_array!(&xs[..3], [u32; 3]), Some(&[1, 2, 4]));
+    assert_eq!(first_array!(&xs, [u32; 2]), Some(&[1, 2]));
+}
+
+#[test]
+fn methods() {
+    let xs: [u32; 6] = [1, 2, 4, 8, 16, 32];
+    assert_eq!(xs[3..].to_array_ref::<3>(), Some(&[8, 16, 32]));
+}